@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: 2023 Ledger SAS
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exchange area copies throughput
+//!
+//! Run with `cargo bench`. Compares the checked and unchecked u8 vector
+//! copies to the area for a few payload lengths. Word multiple lengths use
+//! word-sized area accesses.
+//!
+//! The word and byte copy paths are then compared for small payloads, by
+//! copying at an aligned area offset, taking the word path for word multiple
+//! lengths, and at a misaligned offset, always taking the byte path.
+//!
+//! The crate has no dependencies and must build offline, so this is a plain
+//! timing loop with black_box() rather than a criterion bench. The reported
+//! figures are the mean time per copy over ITERATIONS copies, without
+//! criterion statistical analysis.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use svcexch::{Area, ExchangeArea, EXCHANGE_AREA_LEN};
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench(name: &str, len: usize, mut copy: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        copy();
+    }
+    let elapsed = start.elapsed();
+    let per_copy = elapsed / ITERATIONS;
+    let throughput = (len as f64 * ITERATIONS as f64)
+        / elapsed.max(Duration::from_nanos(1)).as_secs_f64()
+        / (1024.0 * 1024.0);
+    println!("{name:>10} {len:>4} bytes: {per_copy:>10?}/copy {throughput:>10.1} MiB/s");
+}
+
+fn main() {
+    let area = Area::take().expect("exchange area already taken");
+    let buffer = [0x5au8; EXCHANGE_AREA_LEN];
+    for len in [4, 8, 16, 64, EXCHANGE_AREA_LEN] {
+        let src = black_box(buffer.as_ptr());
+        bench("checked", len, || {
+            let _ = black_box(area.copy_vec_to(src, black_box(len)));
+        });
+        bench("unchecked", len, || {
+            // SAFETY: buffer is a local array, it can't overlap the area
+            let _ = black_box(unsafe { area.copy_vec_to_unchecked(src, black_box(len)) });
+        });
+    }
+    for len in [4, 8, 16] {
+        let src = black_box(buffer.as_ptr());
+        bench("aligned", len, || {
+            let _ = black_box(area.copy_vec_to_at(black_box(0), src, black_box(len)));
+        });
+        bench("misaligned", len, || {
+            let _ = black_box(area.copy_vec_to_at(black_box(1), src, black_box(len)));
+        });
+    }
+}
@@ -1,24 +1,395 @@
 // SPDX-FileCopyrightText: 2023 Ledger SAS
 // SPDX-License-Identifier: Apache-2.0
 
-use core::ptr::addr_of_mut;
+use core::cell::{Cell, UnsafeCell};
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "trace")]
+use core::sync::atomic::AtomicPtr;
+use __private::LeFields;
 
-const EXCHANGE_AREA_LEN: usize = 128; // TODO: replace by CONFIG-defined value
+/// Length of the linked exchange area, in bytes
+///
+/// Can be used to size user buffers that are exchanged with the kernel,
+/// e.g. `let mut buf = [0u8; svcexch::EXCHANGE_AREA_LEN];`
+pub const EXCHANGE_AREA_LEN: usize = 128; // TODO: replace by CONFIG-defined value
+
+/// Name of the linker section holding the exchange area
+///
+/// Build scripts and linker script generators can use it to map the
+/// exchange area. The link_section attribute only accepts a literal, so it
+/// must be kept in sync with this value.
+#[cfg(not(feature = "alt-section"))]
+pub const EXCHANGE_SECTION: &str = ".svcexchange";
+
+/// Name of the linker section holding the exchange area, selected by the
+/// alt-section feature
+#[cfg(feature = "alt-section")]
+pub const EXCHANGE_SECTION: &str = ".svcexchange.alt";
+
+/// Common types and traits re-exports
+///
+/// ```
+/// use svcexch::prelude::*;
+///
+/// let area = Area::take().unwrap();
+/// let status = area.write_str("hello");
+/// assert!(matches!(status, Status::Ok));
+/// ```
+pub mod prelude {
+    pub use crate::{
+        Area, Area128, AreaCursor, AreaWriter, CopyMode, ExchangeError, ExchangeArea,
+        ExchangeGuard, Pod, ShmInfo, ShmInfoError, ShmPerms, Status, EXCHANGE_AREA_LEN,
+    };
+}
+
+/// Number of exchange area slots in the backing storage
+///
+/// With the double-buffer feature, the storage holds two EXCHANGE_AREA_LEN
+/// long slots, see Area::swap().
+const AREA_SLOTS: usize = if cfg!(feature = "double-buffer") { 2 } else { 1 };
+
+/// Exchange area backing storage
+///
+/// The kernel may access the exchanged structures fields as aligned words, so
+/// the area is aligned on the largest exchanged type alignment.
+///
+/// The area content is only accessed through raw pointers obtained from the
+/// UnsafeCell, never through references, as the kernel writes it behind the
+/// back of the compiler.
+#[repr(C, align(8))]
+struct AreaStorage(UnsafeCell<[u8; EXCHANGE_AREA_LEN * AREA_SLOTS]>);
+
+// SAFETY: the storage is only accessed through the Area handles bound to it.
+// Safe code can only get such a handle from Area::take(), which hands out a
+// single handle until it and all its clones are dropped. Area is neither Send
+// nor Sync, so these handles, and the references to them, never leave the
+// thread that took the area: the storage is never accessed concurrently. The
+// unsafe Area::new() and C entry points bypass the ownership, and their
+// callers must uphold this single thread access.
+unsafe impl Sync for AreaStorage {}
+
+impl AreaStorage {
+    /// raw pointer to the area first byte
+    const fn as_ptr(&self) -> *mut u8 {
+        self.0.get() as *mut u8
+    }
 
-#[unsafe(link_section = ".svcexchange")]
-static mut EXCHANGE_AREA: [u8; EXCHANGE_AREA_LEN] = [0u8; EXCHANGE_AREA_LEN];
+    /// zero the whole storage, all the slots included, with volatile writes
+    /// so that the compiler can't elide them
+    #[cfg(feature = "zeroize-on-drop")]
+    fn zeroize(&self) {
+        let storage = self.as_ptr();
+        for i in 0..EXCHANGE_AREA_LEN * AREA_SLOTS {
+            unsafe { storage.add(i).write_volatile(0) };
+        }
+    }
+}
+
+/// The exchange area is placed in the EXCHANGE_SECTION section, mapped by the
+/// Sentry image linker script. Host test builds use an ordinary static.
+///
+/// Supported sections are:
+/// - .svcexchange, the default
+/// - .svcexchange.alt, with the alt-section feature
+///
+/// Adding a section requires a new feature, a cfg_attr branch here, and the
+/// matching EXCHANGE_SECTION definition.
+#[cfg_attr(
+    not(any(test, feature = "host-test", feature = "alt-section")),
+    unsafe(link_section = ".svcexchange")
+)]
+#[cfg_attr(
+    all(not(any(test, feature = "host-test")), feature = "alt-section"),
+    unsafe(link_section = ".svcexchange.alt")
+)]
+static EXCHANGE_AREA: AreaStorage =
+    AreaStorage(UnsafeCell::new([0u8; EXCHANGE_AREA_LEN * AREA_SLOTS]));
+
+/// Index of the slot given to the kernel, with the double-buffer feature.
+/// The job copies target the other slot.
+#[cfg(feature = "double-buffer")]
+static ACTIVE_SLOT: AtomicUsize = AtomicUsize::new(0);
+
+/// Exchange area ownership, counting the alive Area handle returned by
+/// Area::take() and its clones. The area is free when the count is 0.
+static TAKEN: AtomicUsize = AtomicUsize::new(0);
+
+/// Exchange area operation reported to the trace hook
+#[cfg(feature = "trace")]
+#[derive(PartialEq,Debug,Clone,Copy)]
+pub enum ExchangeOp {
+    /// copy to the exchange area
+    Write,
+    /// copy from the exchange area
+    Read,
+}
+
+/// Trace hook registered with Area::set_trace_hook(), as a type-erased
+/// fn(ExchangeOp, usize) pointer. Null when no hook is registered.
+#[cfg(feature = "trace")]
+static TRACE_HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
 
 /// test purpose, before moving this crate as uapi module. This
 /// type is defined in the sentry-kernel uapi types module
+///
+/// Beside Ok, each variant gives the precise reason of a copy failure.
+/// Invalid is kept as a catch-all for failures that do not have a more
+/// specific variant.
+///
+/// A dropped status silently ignores a copy failure, so the compiler warns
+/// about unused statuses.
+#[must_use]
+#[derive(Debug)]
 pub enum Status {
     Ok,
     Invalid,
+    /// the data to copy do not fit in the exchange area
+    TooLarge,
+    /// the user buffer overlaps the exchange area
+    Overlap,
+    /// the user buffer pointer is null
+    NullPointer,
+    /// the user buffer pointer is not aligned for the copied type
+    Misaligned,
+    /// the copy length in bytes overflows usize
+    Overflow,
+    /// the bytes read from the exchange area are not valid UTF-8
+    InvalidUtf8,
+    /// more data are read from the exchange area than written to it
+    ShortRead,
+}
+
+/// Behavior of the slice copies when the slice is bigger than the area
+#[derive(PartialEq,Debug,Clone,Copy)]
+pub enum CopyMode {
+    /// the copy fails with Status::TooLarge
+    Strict,
+    /// the copy is clamped to the area length
+    Saturating,
+}
+
+/// Exchange failure reason
+///
+/// This is the error counterpart of the Status failure variants, used by the
+/// Result based try_*() methods, so that exchange steps can be chained with
+/// the `?` operator. Status is kept for the uapi FFI layer.
+#[derive(PartialEq,Debug,Clone,Copy)]
+pub enum ExchangeError {
+    Invalid,
+    /// the data to copy do not fit in the exchange area
+    TooLarge,
+    /// the user buffer overlaps the exchange area
+    Overlap,
+    /// the user buffer pointer is null
+    NullPointer,
+    /// the user buffer pointer is not aligned for the copied type
+    Misaligned,
+    /// the copy length in bytes overflows usize
+    Overflow,
+    /// the bytes read from the exchange area are not valid UTF-8
+    InvalidUtf8,
+    /// more data are read from the exchange area than written to it
+    ShortRead,
+}
+
+/// C representation of the status, for the uapi C glue
+///
+/// Ok is 0, failures are negative values.
+impl From<Status> for i32 {
+    fn from(status: Status) -> i32 {
+        match status {
+            Status::Ok => 0,
+            Status::Invalid => -1,
+            Status::TooLarge => -2,
+            Status::Overlap => -3,
+            Status::NullPointer => -4,
+            Status::Misaligned => -5,
+            Status::Overflow => -6,
+            Status::InvalidUtf8 => -7,
+            Status::ShortRead => -8,
+        }
+    }
+}
+
+impl core::fmt::Display for ExchangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            ExchangeError::Invalid => "invalid exchange",
+            ExchangeError::TooLarge => "data do not fit in exchange area",
+            ExchangeError::Overlap => "buffer overlaps exchange area",
+            ExchangeError::NullPointer => "null buffer pointer",
+            ExchangeError::Misaligned => "misaligned buffer pointer",
+            ExchangeError::Overflow => "copy length overflows",
+            ExchangeError::InvalidUtf8 => "invalid UTF-8 in exchange area",
+            ExchangeError::ShortRead => "read beyond exchange area written data",
+        })
+    }
+}
+
+impl core::error::Error for ExchangeError {}
+
+/// Short status message, the failures giving the ExchangeError message
+impl core::fmt::Display for Status {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let error = match self {
+            Status::Ok => return f.write_str("ok"),
+            Status::Invalid => ExchangeError::Invalid,
+            Status::TooLarge => ExchangeError::TooLarge,
+            Status::Overlap => ExchangeError::Overlap,
+            Status::NullPointer => ExchangeError::NullPointer,
+            Status::Misaligned => ExchangeError::Misaligned,
+            Status::Overflow => ExchangeError::Overflow,
+            Status::InvalidUtf8 => ExchangeError::InvalidUtf8,
+            Status::ShortRead => ExchangeError::ShortRead,
+        };
+        core::fmt::Display::fmt(&error, f)
+    }
+}
+
+impl Status {
+    /// convert the status to a Result, Ok being the only success status
+    pub fn into_result(self) -> Result<(), ExchangeError> {
+        match self {
+            Status::Ok => Ok(()),
+            Status::Invalid => Err(ExchangeError::Invalid),
+            Status::TooLarge => Err(ExchangeError::TooLarge),
+            Status::Overlap => Err(ExchangeError::Overlap),
+            Status::NullPointer => Err(ExchangeError::NullPointer),
+            Status::Misaligned => Err(ExchangeError::Misaligned),
+            Status::Overflow => Err(ExchangeError::Overflow),
+            Status::InvalidUtf8 => Err(ExchangeError::InvalidUtf8),
+            Status::ShortRead => Err(ExchangeError::ShortRead),
+        }
+    }
+
+    /// with the panic-on-error feature, panic on a failure status, giving
+    /// the failed operation, offset and length. Otherwise the status is
+    /// returned as is.
+    #[inline]
+    fn or_panic(self, op: &str, offset: usize, length: usize) -> Status {
+        if cfg!(feature = "panic-on-error") {
+            if let Err(error) = self.into_result() {
+                panic!("{} of {} bytes at offset {} failed: {}", op, length, offset, error);
+            }
+            return Status::Ok;
+        }
+        self
+    }
+
+    /// Sentry kernel uapi status code matching the status
+    ///
+    /// The exchange failures are all invalid syscall arguments from the
+    /// kernel point of view, so they are reported as STATUS_INVALID:
+    ///
+    /// | Status                  | uapi code          |
+    /// |-------------------------|--------------------|
+    /// | Ok                      | STATUS_OK (0)      |
+    /// | any failure             | STATUS_INVALID (1) |
+    ///
+    /// The values must be kept in sync with the uapi types header. This
+    /// differs from the i32 conversion, that keeps the failure reason for the
+    /// uapi C glue.
+    pub fn to_uapi(self) -> u32 {
+        const STATUS_OK: u32 = 0;
+        const STATUS_INVALID: u32 = 1;
+        match self {
+            Status::Ok => STATUS_OK,
+            Status::Invalid
+            | Status::TooLarge
+            | Status::Overlap
+            | Status::NullPointer
+            | Status::Misaligned
+            | Status::Overflow
+            | Status::InvalidUtf8
+            | Status::ShortRead => STATUS_INVALID,
+        }
+    }
+}
+
+/// Shared memory permissions bitfield
+///
+/// The permissions are exchanged with the kernel as a raw u32, see
+/// from_bits() and bits() for the conversion at the copy boundary.
+#[derive(PartialEq,Eq,Debug,Clone,Copy,Default)]
+pub struct ShmPerms(u32);
+
+impl ShmPerms {
+    /// the shared memory can be read
+    pub const READ: ShmPerms = ShmPerms(0x1);
+    /// the shared memory can be written
+    pub const WRITE: ShmPerms = ShmPerms(0x2);
+    /// the shared memory can be mapped
+    pub const MAP: ShmPerms = ShmPerms(0x4);
+    /// the shared memory can be transferred to another task
+    pub const TRANSFER: ShmPerms = ShmPerms(0x8);
+
+    /// no permission
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// all the defined permissions
+    pub const fn all() -> Self {
+        Self(Self::READ.0 | Self::WRITE.0 | Self::MAP.0 | Self::TRANSFER.0)
+    }
+
+    /// convert a raw bitfield, None is returned if an undefined bit is set
+    pub const fn from_bits(bits: u32) -> Option<Self> {
+        if bits & !Self::all().0 != 0 {
+            return None;
+        }
+        Some(Self(bits))
+    }
+
+    /// convert a raw bitfield, undefined bits are kept as is
+    pub const fn from_bits_retain(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// raw bitfield
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// true if all the other permissions are set
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// true if no permission is set
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl core::ops::BitOr for ShmPerms {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl core::ops::BitAnd for ShmPerms {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
 }
 
 /// test purpose, before moving this crate as uapi module. This
 /// type is defined in the sentry-kernel uapi types module, with extern(C)
 /// it order to be readable by the kernel
-#[derive(PartialEq,Debug)]
+///
+/// All fields are plain scalars, so ShmInfo is Copy. The Default value is an
+/// all-zero descriptor, used as a placeholder before reading one from the area.
+///
+/// The fields order and layout follow the kernel shm_infos_t C definition, as
+/// ShmInfo is copied byte per byte to and from the area.
+#[derive(PartialEq,Debug,Clone,Copy,Default)]
+#[repr(C)]
 pub struct ShmInfo {
     handle: u32,
     label: u32,
@@ -27,8 +398,266 @@ pub struct ShmInfo {
     perms: u32,
 }
 
+/// ShmInfo fields accessors
+///
+/// ShmInfo is filled by the kernel, fields are read-only for the job.
+impl ShmInfo {
+    /// create a new shared memory descriptor
+    ///
+    /// Descriptors are returned by the kernel, so this is used to synthesize
+    /// descriptors in tests or to exchange them with the kernel for test
+    /// purpose. No field is checked.
+    ///
+    /// - handle: shared memory handle, as returned by the kernel
+    /// - label: shared memory label, as defined in the device tree
+    /// - base: shared memory base address
+    /// - len: shared memory length, in bytes
+    /// - perms: shared memory permissions bitfield
+    pub const fn new(handle: u32, label: u32, base: usize, len: usize, perms: u32) -> Self {
+        Self {
+            handle,
+            label,
+            base,
+            len,
+            perms,
+        }
+    }
+
+    /// shared memory handle
+    pub fn handle(&self) -> u32 {
+        self.handle
+    }
+
+    /// shared memory label
+    pub fn label(&self) -> u32 {
+        self.label
+    }
+
+    /// shared memory base address
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// shared memory length, in bytes
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// shared memory permissions. Undefined bits set by the kernel are kept.
+    pub fn perms(&self) -> ShmPerms {
+        ShmPerms::from_bits_retain(self.perms)
+    }
+}
+
+/// ShmInfo exchange area representation
+///
+/// Each field is stored in little-endian byte order at its repr(C) offset,
+/// padding bytes are zeroed, see impl_exchange!
+impl ShmInfo {
+    const AREA_SIZE: usize = core::mem::size_of::<ShmInfo>();
+
+    /// maximum number of ShmInfo descriptors the kernel can return in the
+    /// linked exchange area, to size the descriptors arrays
+    pub const MAX_IN_AREA: usize = EXCHANGE_AREA_LEN / Self::AREA_SIZE;
+}
+
+/// ShmInfo validation failure reason
+#[derive(PartialEq,Debug,Clone,Copy)]
+pub enum ShmInfoError {
+    /// the descriptor can't be read from the exchange area
+    Exchange(ExchangeError),
+    /// the shared memory handle is null
+    NullHandle,
+    /// the shared memory length is null
+    EmptyLength,
+    /// the shared memory end address overflows the address space
+    AddressOverflow,
+    /// undefined permission bits are set
+    UndefinedPerms,
+}
+
+impl core::fmt::Display for ShmInfoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ShmInfoError::Exchange(error) => write!(f, "shm info exchange failed: {}", error),
+            ShmInfoError::NullHandle => f.write_str("null shm handle"),
+            ShmInfoError::EmptyLength => f.write_str("empty shm"),
+            ShmInfoError::AddressOverflow => f.write_str("shm end address overflows"),
+            ShmInfoError::UndefinedPerms => f.write_str("undefined shm permissions"),
+        }
+    }
+}
+
+impl core::error::Error for ShmInfoError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ShmInfoError::Exchange(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// ShmInfo sanity checks
+impl ShmInfo {
+    /// check that the descriptor returned by the kernel is sane: the handle
+    /// is not null, the shared memory is not empty and does not wrap around
+    /// the address space, and only defined permissions are set.
+    pub fn validate(&self) -> Result<(), ShmInfoError> {
+        if self.handle == 0 {
+            return Err(ShmInfoError::NullHandle);
+        }
+        if self.len == 0 {
+            return Err(ShmInfoError::EmptyLength);
+        }
+        if self.base.checked_add(self.len).is_none() {
+            return Err(ShmInfoError::AddressOverflow);
+        }
+        if ShmPerms::from_bits(self.perms).is_none() {
+            return Err(ShmInfoError::UndefinedPerms);
+        }
+        Ok(())
+    }
+}
+
+const _: () = assert!(core::mem::align_of::<AreaStorage>() >= core::mem::align_of::<ShmInfo>());
+
+/// kernel shm_infos_t size, for 32 bits (Sentry targets) and 64 bits (host
+/// test builds) platforms
+#[cfg(target_pointer_width = "32")]
+const _: () = assert!(core::mem::size_of::<ShmInfo>() == 20);
+#[cfg(target_pointer_width = "64")]
+const _: () = assert!(core::mem::size_of::<ShmInfo>() == 32);
+
 /// Opaque Exchange zone manipulation object
-pub struct Area { }
+///
+/// N is the exchange zone length, in bytes. It defaults to the length of the
+/// linked exchange area and can be reduced for Sentry builds using a smaller
+/// exchange zone. N can't be bigger than the linked exchange area.
+///
+/// Area is Clone but not Copy: the written length is tracked in a Cell, which
+/// is not Copy, and Area is a Drop type. Each clone tracks its own written
+/// length.
+///
+/// An Area is bound to the linked exchange area, unless created with
+/// from_raw_parts() on caller-provided storage.
+///
+/// Area is neither Send nor Sync, so that the handles bound to the exchange
+/// area are only used from the thread that took it, see Area::take():
+///
+/// ```compile_fail
+/// let area = svcexch::Area::take().unwrap();
+/// std::thread::spawn(move || drop(area));
+/// ```
+pub struct Area<const N: usize = EXCHANGE_AREA_LEN> {
+    /// number of bytes written to the area by the last copy
+    valid_len: Cell<usize>,
+    /// the handle owns the exchange area, see Area::take()
+    owner: bool,
+    /// caller-provided storage, or None for the linked exchange area. The
+    /// raw pointer also makes Area !Send.
+    base: Option<NonNull<u8>>,
+}
+
+/// Exchange zone manipulation object for the default 128 bytes exchange zone
+pub type Area128 = Area<128>;
+
+/// Plain-old-data types, copied as raw bytes by Area::write_vec(),
+/// Area::read_vec() and the exchange_layout! fields
+///
+/// As the rest of the exchange ABI, the values are stored in the area in
+/// little-endian byte order: to_le() and from_le() convert a value from and
+/// to the job byte order, and are no-ops on little-endian targets. The
+/// trait is implemented for the integers, and arrays of Pod types.
+///
+/// A `#[repr(C)]` structure without padding can implement it, converting
+/// each field:
+///
+/// ```
+/// use svcexch::{Area, Pod, Status};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct Range {
+///     start: u32,
+///     len: u32,
+/// }
+///
+/// // SAFETY: Range has no padding, and its fields are integers
+/// unsafe impl Pod for Range {
+///     fn to_le(self) -> Self {
+///         Range { start: self.start.to_le(), len: self.len.to_le() }
+///     }
+///
+///     fn from_le(value: Self) -> Self {
+///         Range { start: u32::from_le(value.start), len: u32::from_le(value.len) }
+///     }
+/// }
+///
+/// let area = Area::take().unwrap();
+/// assert!(matches!(area.write_vec(&[Range { start: 0x100, len: 16 }]), Status::Ok));
+/// ```
+///
+/// Structures with padding bytes, as ShmInfo on 64-bit targets, are copied
+/// with their ExchangeArea implementation instead.
+///
+/// # Safety
+///
+/// The values are copied as raw bytes, so the type must not have padding
+/// bytes, and any bit pattern must be a valid value of the type, e.g. bool
+/// is not a Pod type:
+///
+/// ```compile_fail
+/// use svcexch::{Area, Status};
+///
+/// let area = Area::take().unwrap();
+/// let mut flags = [false; 4];
+/// assert!(matches!(area.read_vec(&mut flags), Status::Ok));
+/// ```
+pub unsafe trait Pod: Copy {
+    /// convert the value from the job byte order to little-endian
+    fn to_le(self) -> Self;
+
+    /// convert the value from little-endian to the job byte order
+    fn from_le(value: Self) -> Self;
+}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(
+            // SAFETY: integers have no padding, and any bit pattern is valid
+            unsafe impl Pod for $t {
+                fn to_le(self) -> Self {
+                    <$t>::to_le(self)
+                }
+
+                fn from_le(value: Self) -> Self {
+                    <$t>::from_le(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+// SAFETY: the unit type has no bytes
+unsafe impl Pod for () {
+    fn to_le(self) -> Self {}
+
+    fn from_le(_: Self) -> Self {}
+}
+
+// SAFETY: arrays have no padding between their elements
+unsafe impl<T: Pod, const M: usize> Pod for [T; M] {
+    fn to_le(self) -> Self {
+        self.map(T::to_le)
+    }
+
+    fn from_le(value: Self) -> Self {
+        value.map(T::from_le)
+    }
+}
 
 /// Public interface to manipulate the kernel/user exchange zone
 ///
@@ -48,190 +677,4128 @@ pub struct Area { }
 /// potential types, so that the area manipulation is naturally made for all
 /// supported kernel/user shared types.
 ///
-pub trait ExhangeArea<T : ?Sized> {
+/// The exchange ABI is little-endian: the scalars, including the structures
+/// fields, are always stored in the area in little-endian byte order, whatever
+/// the job endianness is.
+///
+/// Each typed implementation statically checks that the type fits in the
+/// linked exchange area, so that a type or area size change breaks the build
+/// instead of failing at runtime. Implementations for other types should do
+/// the same:
+///
+/// ```ignore
+/// const _: () = assert!(core::mem::size_of::<MyType>() <= EXCHANGE_AREA_LEN);
+/// ```
+///
+/// With the panic-on-error feature, the failed copies panic instead of
+/// returning a failure status. This applies to all the Area copies, including
+/// the Area inherent methods built on them.
+pub trait ExchangeArea<T : ?Sized> {
 
     /// copy vector object to area. length defines the number of T-typed
     /// data that need to be copied to the shared area.
     /// This is typically used when exchanging strings in the println!
     /// upper layer implementation
-    fn copy_vec_to(&self, _from: *const T, _length: usize) -> Status {
-        Status::Invalid
+    ///
+    /// For all the vector copies, a zero length copy is a no-op returning
+    /// Status::Ok: neither the area nor the pointer are accessed, so the
+    /// pointer may be null, and the written length is left unchanged.
+    fn copy_vec_to(&self, _from: *const T, length: usize) -> Status {
+        Status::Invalid.or_panic("copy to the area", 0, length)
     }
 
     /// copy vector object from area. length defines the number of T-typed
     /// data to be copied to the user job T object.
-    fn copy_vec_from(&self, _from: *mut T, _length: usize) -> Status {
-        Status::Invalid
+    fn copy_vec_from(&self, _from: *mut T, length: usize) -> Status {
+        Status::Invalid.or_panic("copy from the area", 0, length)
+    }
+
+    /// same as copy_vec_to(), but also returns the number of T-typed data
+    /// effectively copied to the shared area. Data that do not fit in the
+    /// area are never truncated, the copy is rejected instead, so the count
+    /// is either length or 0.
+    fn copy_vec_to_counted(&self, _from: *const T, length: usize) -> (Status, usize) {
+        (Status::Invalid.or_panic("copy to the area", 0, length), 0)
+    }
+
+    /// same as copy_vec_from(), but also returns the number of T-typed data
+    /// effectively copied from the shared area.
+    fn copy_vec_from_counted(&self, _from: *mut T, length: usize) -> (Status, usize) {
+        (Status::Invalid.or_panic("copy from the area", 0, length), 0)
+    }
+
+    /// same as copy_vec_to(), but the data are written starting at the given
+    /// byte offset in the area instead of the area start. This is used when
+    /// the syscall expects several fields packed at fixed offsets.
+    fn copy_vec_to_at(&self, offset: usize, _from: *const T, length: usize) -> Status {
+        Status::Invalid.or_panic("copy to the area", offset, length)
+    }
+
+    /// same as copy_vec_from(), but the data are read starting at the given
+    /// byte offset in the area instead of the area start.
+    fn copy_vec_from_at(&self, offset: usize, _to: *mut T, length: usize) -> Status {
+        Status::Invalid.or_panic("copy from the area", offset, length)
     }
+
     /// copy single object of type T to area. This method is used when
     /// delivering user structured data to the kernel.
     fn copy_to(&self, _from: *const T) -> Status {
-        Status::Invalid
+        Status::Invalid.or_panic("copy to the area", 0, 0)
     }
 
     /// copy single object of type T from area. This method is used when
     /// receiving structured data from the kernel.
     fn copy_from(&self, _from: *mut T) -> Status {
-        Status::Invalid
+        Status::Invalid.or_panic("copy from the area", 0, 0)
     }
 
-    /// area length in bytes. Can be used to check that the T-typed data
-    /// is small enough to be exchanged with the kernel. This implementation
-    /// should be the final one and do not need to be defined in various impl.
-    fn area_length(&self) -> usize {
-        EXCHANGE_AREA_LEN
+    /// same as copy_from(), to an uninitialized object. On Status::Ok, the
+    /// object is fully initialized and the caller can assume_init() it.
+    fn copy_from_uninit(&self, to: &mut core::mem::MaybeUninit<T>) -> Status
+    where
+        T: Sized,
+    {
+        self.copy_from(to.as_mut_ptr())
     }
-}
 
-/// Copy ShmInfo from and to the area.
-///
-/// In Sentry real world usage, this structure is returned by the kernel, and
-/// is never written in the area by the userspace job.
-/// The copy_to() is used for test purpose only.
-impl ExhangeArea<ShmInfo> for Area {
+    /// same as copy_vec_to_counted(), returning the number of T-typed data
+    /// copied to the area or the failure reason.
+    fn try_copy_vec_to(&self, from: *const T, length: usize) -> Result<usize, ExchangeError> {
+        let (status, count) = self.copy_vec_to_counted(from, length);
+        status.into_result().map(|_| count)
+    }
 
-    #[allow(static_mut_refs)]
-    fn copy_from(&self, to: *mut ShmInfo) -> Status {
-        unsafe {
-            core::ptr::copy_nonoverlapping(
-                EXCHANGE_AREA.as_ptr(),
-                to as *mut u8,
-                core::mem::size_of::<ShmInfo>().min(EXCHANGE_AREA_LEN),
-            );
-        }
-        Status::Ok
+    /// same as copy_vec_from_counted(), returning the number of T-typed data
+    /// copied from the area or the failure reason.
+    fn try_copy_vec_from(&self, to: *mut T, length: usize) -> Result<usize, ExchangeError> {
+        let (status, count) = self.copy_vec_from_counted(to, length);
+        status.into_result().map(|_| count)
     }
 
-    #[allow(static_mut_refs)]
-    fn copy_to(&self, from: *const ShmInfo) -> Status {
-        unsafe {
-            core::ptr::copy_nonoverlapping(
-                from as *const u8,
-                EXCHANGE_AREA.as_mut_ptr(),
-                core::mem::size_of::<ShmInfo>().min(EXCHANGE_AREA_LEN),
-            );
-        }
-        Status::Ok
+    /// same as copy_to(), returning the failure reason as an error
+    fn try_copy_to(&self, from: *const T) -> Result<(), ExchangeError> {
+        self.copy_to(from).into_result()
     }
-}
 
-/// Copy u8 vector from and to the area.
-///
-/// The copy_to() and copy_from() is not implemented as there is no need,
-/// by now, for single u8 copy.
-impl ExhangeArea<u8> for Area {
+    /// same as copy_from(), returning the failure reason as an error
+    fn try_copy_from(&self, to: *mut T) -> Result<(), ExchangeError> {
+        self.copy_from(to).into_result()
+    }
 
-    #[allow(static_mut_refs)]
-    fn copy_vec_to(&self, from: *const u8, length: usize) -> Status {
-        unsafe {
-            if Area::check_overlapping(from, length).is_err() {
-                return Status::Invalid;
-            }
-            core::ptr::copy_nonoverlapping(
-                from,
-                EXCHANGE_AREA.as_mut_ptr(),
-                length.min(EXCHANGE_AREA_LEN),
-            );
-        }
-        Status::Ok
+    /// area length in bytes. Can be used to check that the T-typed data
+    /// is small enough to be exchanged with the kernel. Implementations for
+    /// Area<N> return N.
+    fn area_length(&self) -> usize {
+        EXCHANGE_AREA_LEN
     }
 
-    #[allow(static_mut_refs)]
-    fn copy_vec_from(&self, to: *mut u8, length: usize) -> Status {
-        unsafe {
-            if Area::check_overlapping(to, length).is_err() {
-                return Status::Invalid;
-            }
-            core::ptr::copy_nonoverlapping(
-                EXCHANGE_AREA.as_ptr(),
-                to,
-                length.min(EXCHANGE_AREA_LEN),
-            );
+    /// number of T-typed data that fit in the area. Can be used to bound the
+    /// length given to copy_vec_to() and copy_vec_from().
+    /// Zero-sized types never consume area space, so an unbounded number of
+    /// them fit, and usize::MAX is returned.
+    fn area_capacity(&self) -> usize
+    where
+        T: Sized,
+    {
+        match core::mem::size_of::<T>() {
+            0 => usize::MAX,
+            size => self.area_length() / size,
         }
-        Status::Ok
     }
 }
 
-/// Non-trait relative utility functions implementation for Area
+/// Former, misspelled, name of the ExchangeArea trait
 ///
-/// Here are defined local functions only, used as helper for trait methods
-/// implementations.
-impl Area {
+/// Kept so that existing code keeps building. Use ExchangeArea instead.
+#[deprecated(note = "renamed to ExchangeArea")]
+pub use ExchangeArea as ExhangeArea;
 
-    /// create a new Area object. By now, there is no specific metadata in this
-    /// object
-    fn new() -> Self {
-        Self { }
-    }
+/// Implement the single object and vector copy methods of ExchangeArea for a
+/// structure, as ShmInfo.
+///
+/// The structure fields are listed with their types, and each field is
+/// serialized in little-endian byte order at its offset in the structure,
+/// padding bytes being zeroed, so that the area content does not depend on
+/// the job endianness. Objects are never copied with their in-memory
+/// representation. The fields must be integers, and all of them must be
+/// listed, which is checked at build time.
+///
+/// The user buffer must be aligned for the type.
+/// The type is statically checked to be Copy and small enough to fit in the
+/// linked exchange area. Copies are still rejected at runtime for Area<N>
+/// zones smaller than the type.
+///
+/// The optional `write:` attributes are applied to the methods copying to the
+/// area only, e.g. to restrict them to test builds.
+///
+/// ```
+/// use svcexch::{impl_exchange, Area, ExchangeArea, Status};
+///
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// #[repr(C)]
+/// pub struct Range {
+///     start: u32,
+///     len: u64,
+/// }
+///
+/// impl_exchange!(Range { start: u32, len: u64 });
+///
+/// let area = Area::take().unwrap();
+/// let range = Range { start: 0x100, len: 16 };
+/// assert!(matches!(area.copy_to(&range), Status::Ok));
+/// let mut copy = Range { start: 0, len: 0 };
+/// assert!(matches!(area.copy_from(&mut copy), Status::Ok));
+/// assert_eq!(copy, range);
+/// ```
+#[macro_export]
+macro_rules! impl_exchange {
+    (
+        $(#[$meta:meta])*
+        $t:ident { $($field:ident: $ft:ty),+ $(,)? }
+        $(, write: $(#[$write_meta:meta])*)? $(,)?
+    ) => {
+        const _: () = {
+            const fn assert_copy<T: Copy>() {}
+            assert_copy::<$t>();
+            assert!(core::mem::size_of::<$t>() <= $crate::EXCHANGE_AREA_LEN);
+        };
 
-    /// check that the given vector do not overlap with the exchange area
-    ///
-    /// This is required in order to use the cop_nonoverlapping() API safely.
-    #[allow(static_mut_refs)]
-    unsafe fn check_overlapping(pointer: *const u8, length: usize) -> Result<(), ()> {
-        let area = EXCHANGE_AREA.as_ptr();
-        let area_end = area.add(EXCHANGE_AREA_LEN);
+        impl $crate::__private::LeFields for $t {
+            fn put_le(&self, bytes: &mut [u8]) {
+                $(
+                    let offset = core::mem::offset_of!($t, $field);
+                    let field = self.$field.to_le_bytes();
+                    bytes[offset..offset + field.len()].copy_from_slice(&field);
+                )+
+            }
 
-        // buffer starts in the middle of the exchange area, abort
-        if pointer >= area && pointer <= area_end {
-            return Err(());
+            fn get_le(bytes: &[u8]) -> Self {
+                Self {
+                    $(
+                        $field: <$ft>::from_le_bytes($crate::__private::le_field(
+                            bytes,
+                            core::mem::offset_of!($t, $field),
+                        )),
+                    )+
+                }
+            }
         }
 
-        // buffer ends in the exchange area, abort
-        // Note: this is unlikely to happen if `svc_exchange` is always assumed to be at
-        // the beginning of RAM
-        let buffer_end = pointer.add(length);
-        if buffer_end >= area && buffer_end <= area_end {
-            return Err(());
-        }
+        $(#[$meta])*
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        impl<const N: usize> $crate::ExchangeArea<$t> for $crate::Area<N> {
 
-        // exchange area is contained within the buffer, abort
-        if pointer <= area && buffer_end >= area_end {
-            return Err(());
-        }
-        Ok(())
-    }
-}
+            fn copy_from(&self, to: *mut $t) -> $crate::Status {
+                self.copy_fields_from(to)
+            }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            $($(#[$write_meta])*)?
+            fn copy_to(&self, from: *const $t) -> $crate::Status {
+                self.copy_fields_to(from)
+            }
 
-    #[test]
-    fn create_area() {
-        let area = Area::new();
-        assert_eq!(<Area as ExhangeArea<u8>>::area_length(&area), 128);
-    }
+            $($(#[$write_meta])*)?
+            fn copy_vec_to(&self, from: *const $t, length: usize) -> $crate::Status {
+                self.copy_fields_vec_to_counted(from, length).0
+            }
 
-    #[test]
-    fn back_to_back_copy() {
-        let area = Area::new();
+            fn copy_vec_from(&self, to: *mut $t, length: usize) -> $crate::Status {
+                self.copy_fields_vec_from_counted(to, length).0
+            }
+
+            $($(#[$write_meta])*)?
+            fn copy_vec_to_counted(&self, from: *const $t, length: usize) -> ($crate::Status, usize) {
+                self.copy_fields_vec_to_counted(from, length)
+            }
+
+            fn copy_vec_from_counted(&self, to: *mut $t, length: usize) -> ($crate::Status, usize) {
+                self.copy_fields_vec_from_counted(to, length)
+            }
+
+            fn area_length(&self) -> usize {
+                N
+            }
+        }
+    };
+}
+
+/// impl_exchange! support, not part of the crate API
+#[doc(hidden)]
+pub mod __private {
+    /// field by field little-endian serialization, implemented by
+    /// impl_exchange!
+    pub trait LeFields: Copy {
+        /// serialize the fields to bytes, size_of::<Self>() bytes long
+        fn put_le(&self, bytes: &mut [u8]);
+
+        /// deserialize the fields from bytes, size_of::<Self>() bytes long
+        fn get_le(bytes: &[u8]) -> Self;
+    }
+
+    /// the L bytes long field at offset in bytes
+    pub fn le_field<const L: usize>(bytes: &[u8], offset: usize) -> [u8; L] {
+        let mut field = [0u8; L];
+        field.copy_from_slice(&bytes[offset..offset + L]);
+        field
+    }
+}
+
+/// Field by field copies implementation, see impl_exchange!
+///
+/// These methods are only public for the impl_exchange! expansion in other
+/// crates, use the ExchangeArea methods instead.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+impl<const N: usize> Area<N> {
+
+    #[doc(hidden)]
+    pub fn copy_fields_from<T: LeFields>(&self, to: *mut T) -> Status {
+        let size = core::mem::size_of::<T>();
+        let status = 'copy: {
+            if to.is_null() {
+                break 'copy Status::NullPointer;
+            }
+            if Self::check_alignment(to).is_err() {
+                break 'copy Status::Misaligned;
+            }
+            if size > N {
+                break 'copy Status::TooLarge;
+            }
+            let mut bytes = [0u8; N];
+            let bytes = &mut bytes[..size];
+            unsafe {
+                if self.check_overlapping(to as *const u8, size).is_err() {
+                    break 'copy Status::Overlap;
+                }
+                if self.area_read(bytes.as_mut_ptr(), size).is_err() {
+                    break 'copy Status::ShortRead;
+                }
+                to.write(T::get_le(bytes));
+            }
+            Status::Ok
+        };
+        status.or_panic("copy from the area", 0, size)
+    }
+
+    #[doc(hidden)]
+    pub fn copy_fields_to<T: LeFields>(&self, from: *const T) -> Status {
+        let size = core::mem::size_of::<T>();
+        let status = 'copy: {
+            if from.is_null() {
+                break 'copy Status::NullPointer;
+            }
+            if Self::check_alignment(from).is_err() {
+                break 'copy Status::Misaligned;
+            }
+            if size > N {
+                break 'copy Status::TooLarge;
+            }
+            let mut bytes = [0u8; N];
+            let bytes = &mut bytes[..size];
+            unsafe {
+                if self.check_overlapping(from as *const u8, size).is_err() {
+                    break 'copy Status::Overlap;
+                }
+                from.read().put_le(bytes);
+                self.area_write(bytes.as_ptr(), size);
+            }
+            Status::Ok
+        };
+        status.or_panic("copy to the area", 0, size)
+    }
+
+    #[doc(hidden)]
+    pub fn copy_fields_vec_to_counted<T: LeFields>(
+        &self,
+        from: *const T,
+        length: usize,
+    ) -> (Status, usize) {
+        let (status, count) = 'copy: {
+            if length == 0 {
+                break 'copy (Status::Ok, 0);
+            }
+            if from.is_null() {
+                break 'copy (Status::NullPointer, 0);
+            }
+            if Self::check_alignment(from).is_err() {
+                break 'copy (Status::Misaligned, 0);
+            }
+            let size = match length.checked_mul(core::mem::size_of::<T>()) {
+                Some(size) if size <= N => size,
+                Some(_) => break 'copy (Status::TooLarge, 0),
+                None => break 'copy (Status::Overflow, 0),
+            };
+            let mut bytes = [0u8; N];
+            unsafe {
+                if self.check_overlapping(from as *const u8, size).is_err() {
+                    break 'copy (Status::Overlap, 0);
+                }
+                let elems = bytes[..size].chunks_exact_mut(core::mem::size_of::<T>());
+                for (i, bytes) in elems.enumerate() {
+                    from.add(i).read().put_le(bytes);
+                }
+                self.area_write(bytes.as_ptr(), size);
+            }
+            (Status::Ok, length)
+        };
+        let size = length.saturating_mul(core::mem::size_of::<T>());
+        (status.or_panic("copy to the area", 0, size), count)
+    }
+
+    #[doc(hidden)]
+    pub fn copy_fields_vec_from_counted<T: LeFields>(
+        &self,
+        to: *mut T,
+        length: usize,
+    ) -> (Status, usize) {
+        let (status, count) = 'copy: {
+            if length == 0 {
+                break 'copy (Status::Ok, 0);
+            }
+            if to.is_null() {
+                break 'copy (Status::NullPointer, 0);
+            }
+            if Self::check_alignment(to).is_err() {
+                break 'copy (Status::Misaligned, 0);
+            }
+            let size = match length.checked_mul(core::mem::size_of::<T>()) {
+                Some(size) if size <= N => size,
+                Some(_) => break 'copy (Status::TooLarge, 0),
+                None => break 'copy (Status::Overflow, 0),
+            };
+            let mut bytes = [0u8; N];
+            unsafe {
+                if self.check_overlapping(to as *const u8, size).is_err() {
+                    break 'copy (Status::Overlap, 0);
+                }
+                // the whole range is read at once, so that a short read does
+                // not leave a partial copy
+                if self.area_read(bytes.as_mut_ptr(), size).is_err() {
+                    break 'copy (Status::ShortRead, 0);
+                }
+                let elems = bytes[..size].chunks_exact(core::mem::size_of::<T>());
+                for (i, bytes) in elems.enumerate() {
+                    to.add(i).write(T::get_le(bytes));
+                }
+            }
+            (Status::Ok, length)
+        };
+        let size = length.saturating_mul(core::mem::size_of::<T>());
+        (status.or_panic("copy from the area", 0, size), count)
+    }
+}
+
+impl_exchange!(
+    /// Copy ShmInfo from and to the area.
+    ///
+    /// In Sentry real world usage, this structure is returned by the kernel, and
+    /// is never written in the area by the userspace job.
+    /// The copies to the area are used for test purpose only, and are only
+    /// available in the crate tests or with the test-helpers feature, so that
+    /// a job can't forge descriptors. Otherwise they return Status::Invalid.
+    ShmInfo { handle: u32, label: u32, base: usize, len: usize, perms: u32 },
+    write: #[cfg(any(test, feature = "test-helpers"))]
+);
+
+/// Copy u8 vector from and to the area.
+///
+/// The copy_to() and copy_from() is not implemented as there is no need,
+/// by now, for single u8 copy.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+impl<const N: usize> ExchangeArea<u8> for Area<N> {
+
+    #[inline]
+    fn copy_vec_to(&self, from: *const u8, length: usize) -> Status {
+        self.copy_vec_to_counted(from, length).0
+    }
+
+    #[inline]
+    fn copy_vec_from(&self, to: *mut u8, length: usize) -> Status {
+        self.copy_vec_from_counted(to, length).0
+    }
+
+    #[inline]
+    fn copy_vec_to_counted(&self, from: *const u8, length: usize) -> (Status, usize) {
+        let (status, count) = self.vec_to_counted(from, length);
+        (status.or_panic("copy to the area", 0, length), count)
+    }
+
+    #[inline]
+    fn copy_vec_from_counted(&self, to: *mut u8, length: usize) -> (Status, usize) {
+        let (status, count) = self.vec_from_counted(to, length);
+        (status.or_panic("copy from the area", 0, length), count)
+    }
+
+    fn copy_vec_to_at(&self, offset: usize, from: *const u8, length: usize) -> Status {
+        self.vec_to_at(offset, from, length)
+            .or_panic("copy to the area", offset, length)
+    }
+
+    fn copy_vec_from_at(&self, offset: usize, to: *mut u8, length: usize) -> Status {
+        self.vec_from_at(offset, to, length)
+            .or_panic("copy from the area", offset, length)
+    }
+
+    fn area_length(&self) -> usize {
+        N
+    }
+}
+
+/// u8 vector copies implementation, see ExchangeArea<u8>
+impl<const N: usize> Area<N> {
+
+    #[inline]
+    fn vec_to_counted(&self, from: *const u8, length: usize) -> (Status, usize) {
+        if length == 0 {
+            return (Status::Ok, 0);
+        }
+        if from.is_null() {
+            return (Status::NullPointer, 0);
+        }
+        if length > N {
+            return (Status::TooLarge, 0);
+        }
+        unsafe {
+            if self.check_overlapping(from, length).is_err() {
+                return (Status::Overlap, 0);
+            }
+            self.area_write(from, length);
+        }
+        (Status::Ok, length)
+    }
+
+    #[inline]
+    fn vec_from_counted(&self, to: *mut u8, length: usize) -> (Status, usize) {
+        if length == 0 {
+            return (Status::Ok, 0);
+        }
+        if to.is_null() {
+            return (Status::NullPointer, 0);
+        }
+        if length > N {
+            return (Status::TooLarge, 0);
+        }
+        unsafe {
+            if self.check_overlapping(to, length).is_err() {
+                return (Status::Overlap, 0);
+            }
+            if self.area_read(to, length).is_err() {
+                return (Status::ShortRead, 0);
+            }
+        }
+        (Status::Ok, length)
+    }
+
+    fn vec_to_at(&self, offset: usize, from: *const u8, length: usize) -> Status {
+        if length == 0 {
+            return Status::Ok;
+        }
+        if from.is_null() {
+            return Status::NullPointer;
+        }
+        match offset.checked_add(length) {
+            Some(end) if end <= N => (),
+            Some(_) => return Status::TooLarge,
+            None => return Status::Overflow,
+        }
+        unsafe {
+            if self.check_overlapping(from, length).is_err() {
+                return Status::Overlap;
+            }
+            self.area_write_at(offset, from, length);
+        }
+        Status::Ok
+    }
+
+    fn vec_from_at(&self, offset: usize, to: *mut u8, length: usize) -> Status {
+        if length == 0 {
+            return Status::Ok;
+        }
+        if to.is_null() {
+            return Status::NullPointer;
+        }
+        match offset.checked_add(length) {
+            Some(end) if end <= N => (),
+            Some(_) => return Status::TooLarge,
+            None => return Status::Overflow,
+        }
+        unsafe {
+            if self.check_overlapping(to, length).is_err() {
+                return Status::Overlap;
+            }
+            if self.area_read_at(offset, to, length).is_err() {
+                return Status::ShortRead;
+            }
+        }
+        Status::Ok
+    }
+}
+
+/// Copy fixed-size byte arrays from and to the area.
+///
+/// The copy length is the array length M. Arrays bigger than the linked
+/// exchange area are rejected at build time, and copies are still rejected
+/// at runtime for Area<N> zones smaller than the array.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+impl<const N: usize, const M: usize> ExchangeArea<[u8; M]> for Area<N> {
+
+    fn copy_from(&self, to: *mut [u8; M]) -> Status {
+        let status = 'copy: {
+            const { assert!(M <= EXCHANGE_AREA_LEN) };
+            if to.is_null() {
+                break 'copy Status::NullPointer;
+            }
+            if M > N {
+                break 'copy Status::TooLarge;
+            }
+            unsafe {
+                if self.check_overlapping(to as *const u8, M).is_err() {
+                    break 'copy Status::Overlap;
+                }
+                if self.area_read(to as *mut u8, M).is_err() {
+                    break 'copy Status::ShortRead;
+                }
+            }
+            Status::Ok
+        };
+        status.or_panic("copy from the area", 0, M)
+    }
+
+    fn copy_to(&self, from: *const [u8; M]) -> Status {
+        let status = 'copy: {
+            const { assert!(M <= EXCHANGE_AREA_LEN) };
+            if from.is_null() {
+                break 'copy Status::NullPointer;
+            }
+            if M > N {
+                break 'copy Status::TooLarge;
+            }
+            unsafe {
+                if self.check_overlapping(from as *const u8, M).is_err() {
+                    break 'copy Status::Overlap;
+                }
+                self.area_write(from as *const u8, M);
+            }
+            Status::Ok
+        };
+        status.or_panic("copy to the area", 0, M)
+    }
+
+    fn area_length(&self) -> usize {
+        N
+    }
+}
+
+/// Implement the single object copy methods of ExchangeArea for an integer
+/// type.
+///
+/// The kernel reads these integers from the area, so they are always stored
+/// in little-endian byte order, whatever the job endianness is. The user
+/// buffer does not need to be aligned.
+macro_rules! impl_exchange_le {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            const _: () = assert!(core::mem::size_of::<$t>() <= EXCHANGE_AREA_LEN);
+
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
+            impl<const N: usize> ExchangeArea<$t> for Area<N> {
+
+                fn copy_from(&self, to: *mut $t) -> Status {
+                    let status = 'copy: {
+                        if to.is_null() {
+                            break 'copy Status::NullPointer;
+                        }
+                        if core::mem::size_of::<$t>() > N {
+                            break 'copy Status::TooLarge;
+                        }
+                        let mut bytes = [0u8; core::mem::size_of::<$t>()];
+                        unsafe {
+                            if self.check_overlapping(to as *const u8, bytes.len()).is_err() {
+                                break 'copy Status::Overlap;
+                            }
+                            if self.area_read(bytes.as_mut_ptr(), bytes.len()).is_err() {
+                                break 'copy Status::ShortRead;
+                            }
+                            to.write_unaligned(<$t>::from_le_bytes(bytes));
+                        }
+                        Status::Ok
+                    };
+                    status.or_panic("copy from the area", 0, core::mem::size_of::<$t>())
+                }
+
+                fn copy_to(&self, from: *const $t) -> Status {
+                    let status = 'copy: {
+                        if from.is_null() {
+                            break 'copy Status::NullPointer;
+                        }
+                        if core::mem::size_of::<$t>() > N {
+                            break 'copy Status::TooLarge;
+                        }
+                        unsafe {
+                            let size = core::mem::size_of::<$t>();
+                            if self.check_overlapping(from as *const u8, size).is_err() {
+                                break 'copy Status::Overlap;
+                            }
+                            let bytes = from.read_unaligned().to_le_bytes();
+                            self.area_write(bytes.as_ptr(), bytes.len());
+                        }
+                        Status::Ok
+                    };
+                    status.or_panic("copy to the area", 0, core::mem::size_of::<$t>())
+                }
+
+                fn area_length(&self) -> usize {
+                    N
+                }
+            }
+        )+
+    };
+}
+
+impl_exchange_le!(u16, u32, u64, i16, i32, i64);
+
+/// Safe slice-based interface to the area
+///
+/// These methods derive the copy length from the slice itself, and are
+/// the preferred entry points for Rust callers. The raw pointer based trait
+/// methods are kept for the FFI path.
+impl<const N: usize> Area<N> {
+
+    /// copy the whole src slice to the area. The slice must not be bigger
+    /// than the area length.
+    pub fn write_slice(&self, src: &[u8]) -> Status {
+        self.copy_vec_to(src.as_ptr(), src.len())
+    }
+
+    /// fill the whole dst slice from the area. The slice must not be bigger
+    /// than the area length.
+    pub fn read_slice(&self, dst: &mut [u8]) -> Status {
+        self.copy_vec_from(dst.as_mut_ptr(), dst.len())
+    }
+
+    /// move len bytes inside the area, from the from offset to the to offset.
+    /// Both ranges must fit in the area length, and may overlap. The written
+    /// length is extended up to the end of the destination range, if needed.
+    ///
+    /// The source range is read as any other area read, so with the
+    /// strict-read feature it must have been written. The bytes go through
+    /// a stack copy, so that the overlapping ranges are accessed with the
+    /// same volatile copies as the other area accesses.
+    pub fn move_within(&self, from: usize, to: usize, len: usize) -> Status {
+        let status = 'copy: {
+            for offset in [from, to] {
+                match offset.checked_add(len) {
+                    Some(end) if end <= N => (),
+                    Some(_) => break 'copy Status::TooLarge,
+                    None => break 'copy Status::Overflow,
+                }
+            }
+            let mut bytes = [0u8; N];
+            unsafe {
+                if self.area_read_at(from, bytes.as_mut_ptr(), len).is_err() {
+                    break 'copy Status::ShortRead;
+                }
+                self.area_write_at(to, bytes.as_ptr(), len);
+            }
+            Status::Ok
+        };
+        status.or_panic("move within the area", to, len)
+    }
+
+    /// fill the dst slice from the area with the written data, see
+    /// written_len(), and return the number of copied bytes. The count is
+    /// bounded by the dst length, the area length and the written length,
+    /// so a dst bigger than the written data is only partially filled.
+    ///
+    /// Data written by the kernel are not accounted in the written length,
+    /// use read_slice() to read them.
+    pub fn read_slice_counted(&self, dst: &mut [u8]) -> (Status, usize) {
+        let length = dst.len().min(N).min(self.written_len());
+        self.copy_vec_from_counted(dst.as_mut_ptr(), length)
+    }
+
+    /// copy the src slice to the area, in the given mode, and return the
+    /// number of copied bytes. In Strict mode, this is the same as
+    /// write_slice(). In Saturating mode, only the first area length bytes
+    /// of a bigger slice are copied.
+    pub fn write_slice_with(&self, src: &[u8], mode: CopyMode) -> Result<usize, Status> {
+        let length = match mode {
+            CopyMode::Strict => src.len(),
+            CopyMode::Saturating => src.len().min(N),
+        };
+        match self.copy_vec_to(src.as_ptr(), length) {
+            Status::Ok => Ok(length),
+            status => Err(status),
+        }
+    }
+
+    /// fill the dst slice from the area, in the given mode, and return the
+    /// number of copied bytes. In Strict mode, this is the same as
+    /// read_slice(). In Saturating mode, only the first area length bytes
+    /// of a bigger slice are filled.
+    pub fn read_slice_with(&self, dst: &mut [u8], mode: CopyMode) -> Result<usize, Status> {
+        let length = match mode {
+            CopyMode::Strict => dst.len(),
+            CopyMode::Saturating => dst.len().min(N),
+        };
+        match self.copy_vec_from(dst.as_mut_ptr(), length) {
+            Status::Ok => Ok(length),
+            status => Err(status),
+        }
+    }
+
+    /// copy the whole src slice to the area, right after the written data,
+    /// see written_len(). The written length is extended accordingly. The
+    /// slice must fit in the remaining area space.
+    pub fn append(&self, src: &[u8]) -> Status {
+        self.copy_vec_to_at(self.written_len(), src.as_ptr(), src.len())
+    }
+
+    /// copy the src slice to the area, starting at offset. The copy is
+    /// clamped to the area end, and the number of bytes actually written is
+    /// returned. The offset must not be bigger than the area length.
+    pub fn write_bytes_at(&self, offset: usize, src: &[u8]) -> Result<usize, Status> {
+        if offset > N {
+            return Err(Status::TooLarge.or_panic("copy to the area", offset, src.len()));
+        }
+        let length = src.len().min(N - offset);
+        match self.copy_vec_to_at(offset, src.as_ptr(), length) {
+            Status::Ok => Ok(length),
+            status => Err(status),
+        }
+    }
+
+    /// same as copy_vec_to() for u8 vectors, without checking that the user
+    /// buffer does not overlap the exchange area
+    ///
+    /// This is meant for hot loops copying from a buffer that is already
+    /// known not to overlap the area. The null pointer check is still made,
+    /// and a zero length copy is a no-op, as for copy_vec_to(). Contrary to
+    /// copy_vec_to(), a len bigger than the area length is not rejected but
+    /// clamped to the area length: only the first area length bytes of src
+    /// are copied, see written_len().
+    ///
+    /// # Safety
+    ///
+    /// src must be valid for reads of len bytes, and the src..src+len range
+    /// must not overlap the exchange area.
+    pub unsafe fn copy_vec_to_unchecked(&self, src: *const u8, len: usize) -> Status {
+        if len == 0 {
+            return Status::Ok;
+        }
+        if src.is_null() {
+            return Status::NullPointer.or_panic("copy to the area", 0, len);
+        }
+        self.area_write(src, len.min(N));
+        Status::Ok
+    }
+
+    /// copy the UTF-8 bytes of the string to the area. The length is the
+    /// string length in bytes, not in chars, and must not be bigger than the
+    /// area length.
+    pub fn write_str(&self, s: &str) -> Status {
+        self.write_slice(s.as_bytes())
+    }
+
+    /// copy len bytes from the area to dst, and return them as a string
+    /// slice of dst. len must not be bigger than the area length nor than
+    /// the dst length. The copied bytes are checked to be valid UTF-8.
+    pub fn read_str<'b>(&self, dst: &'b mut [u8], len: usize) -> Result<&'b str, Status> {
+        let Some(dst) = dst.get_mut(..len) else {
+            return Err(Status::TooLarge.or_panic("copy from the area", 0, len));
+        };
+        match self.read_slice(dst) {
+            Status::Ok => (),
+            status => return Err(status),
+        }
+        core::str::from_utf8(dst)
+            .map_err(|_| Status::InvalidUtf8.or_panic("copy from the area", 0, len))
+    }
+
+    /// read a single T-typed object from the area and return it by value
+    ///
+    /// This is the by-value counterpart of copy_from(), for types with an
+    /// ExchangeArea implementation.
+    pub fn read<T: Copy>(&self) -> Result<T, Status>
+    where
+        Self: ExchangeArea<T>,
+    {
+        let mut value = core::mem::MaybeUninit::<T>::uninit();
+        match self.copy_from_uninit(&mut value) {
+            // SAFETY: copy_from_uninit() fully initialized the object
+            Status::Ok => Ok(unsafe { value.assume_init() }),
+            status => Err(status),
+        }
+    }
+
+    /// number of T-typed elements that fit in the area, at compile time
+    ///
+    /// This is the const counterpart of ExchangeArea::area_capacity(), usable
+    /// to size the slices given to write_vec() and read_vec():
+    ///
+    /// ```
+    /// use svcexch::{Area, Status};
+    ///
+    /// let words = [0u32; <Area>::max_elems::<u32>()];
+    /// assert!(matches!(Area::take().unwrap().write_vec(&words), Status::Ok));
+    /// ```
+    pub const fn max_elems<T>() -> usize {
+        match core::mem::size_of::<T>() {
+            0 => usize::MAX,
+            size => N / size,
+        }
+    }
+
+    /// copy the whole src slice of any Pod type to the area. The slice size
+    /// in bytes must not be bigger than the area length.
+    ///
+    /// Each element is stored in little-endian byte order, see Pod. The
+    /// method is not named copy_vec_to() so that it does not shadow the trait
+    /// method. As for the vector copies, an empty copy is a no-op that leaves
+    /// the written length unchanged.
+    pub fn write_vec<T: Pod>(&self, src: &[T]) -> Status {
+        let status = 'copy: {
+            let size = match src.len().checked_mul(core::mem::size_of::<T>()) {
+                Some(0) => break 'copy Status::Ok,
+                Some(size) if size <= N => size,
+                Some(_) => break 'copy Status::TooLarge,
+                None => break 'copy Status::Overflow,
+            };
+            if self.check_overlapping(src.as_ptr() as *const u8, size).is_err() {
+                break 'copy Status::Overlap;
+            }
+            let mut bytes = [0u8; N];
+            let elems = bytes.as_mut_ptr() as *mut T;
+            unsafe {
+                for (i, value) in src.iter().enumerate() {
+                    elems.add(i).write_unaligned(value.to_le());
+                }
+                self.area_write(bytes.as_ptr(), size);
+            }
+            Status::Ok
+        };
+        status.or_panic("copy to the area", 0, core::mem::size_of_val(src))
+    }
+
+    /// fill the whole dst slice of any Pod type from the area, see
+    /// write_vec(). The slice size in bytes must not be bigger than the area
+    /// length.
+    pub fn read_vec<T: Pod>(&self, dst: &mut [T]) -> Status {
+        let status = 'copy: {
+            let size = match dst.len().checked_mul(core::mem::size_of::<T>()) {
+                Some(size) if size <= N => size,
+                Some(_) => break 'copy Status::TooLarge,
+                None => break 'copy Status::Overflow,
+            };
+            if self.check_overlapping(dst.as_ptr() as *const u8, size).is_err() {
+                break 'copy Status::Overlap;
+            }
+            let mut bytes = [0u8; N];
+            let elems = bytes.as_ptr() as *const T;
+            unsafe {
+                if self.area_read(bytes.as_mut_ptr(), size).is_err() {
+                    break 'copy Status::ShortRead;
+                }
+                for (i, value) in dst.iter_mut().enumerate() {
+                    // any bit pattern is a valid Pod value
+                    *value = T::from_le(elems.add(i).read_unaligned());
+                }
+            }
+            Status::Ok
+        };
+        status.or_panic("copy from the area", 0, core::mem::size_of_val(dst))
+    }
+
+    /// same as read_vec(), for a payload length given in bytes, e.g. as
+    /// returned by the kernel. The first byte_len / size_of::<T>() elements
+    /// of dst are filled. As with read_vec(), T must be a Pod type.
+    ///
+    /// A byte length that is not a multiple of the element size would give
+    /// a partial last element, and is rejected with Status::Invalid. A byte
+    /// length bigger than dst is rejected with Status::TooLarge.
+    pub fn read_vec_bytes<T: Pod>(&self, dst: &mut [T], byte_len: usize) -> Status {
+        let status = 'copy: {
+            let count = match core::mem::size_of::<T>() {
+                0 if byte_len == 0 => 0,
+                size if size != 0 && byte_len.is_multiple_of(size) => byte_len / size,
+                _ => break 'copy Status::Invalid,
+            };
+            match dst.get_mut(..count) {
+                Some(dst) => self.read_vec(dst),
+                None => Status::TooLarge,
+            }
+        };
+        status.or_panic("copy from the area", 0, byte_len)
+    }
+
+    /// read the byte at the given area offset
+    pub fn peek_u8(&self, offset: usize) -> Result<u8, Status> {
+        self.peek::<1>(offset).map(|bytes| bytes[0])
+    }
+
+    /// read the little-endian u32 at the given area offset. The offset does
+    /// not need to be aligned.
+    pub fn peek_u32_le(&self, offset: usize) -> Result<u32, Status> {
+        self.peek::<4>(offset).map(u32::from_le_bytes)
+    }
+
+    /// copy the regions back to back to the area, from the area start, and
+    /// return the total written length. The regions must fit together in
+    /// the area, otherwise nothing is written.
+    pub fn scatter_write(&self, regions: &[&[u8]]) -> Result<usize, Status> {
+        let mut total = 0usize;
+        for region in regions {
+            total = match total.checked_add(region.len()) {
+                Some(total) if total <= N => total,
+                Some(total) => {
+                    return Err(Status::TooLarge.or_panic("copy to the area", 0, total))
+                }
+                None => {
+                    return Err(Status::Overflow.or_panic("copy to the area", 0, usize::MAX))
+                }
+            };
+        }
+        self.reset();
+        let mut offset = 0;
+        for region in regions {
+            match self.copy_vec_to_at(offset, region.as_ptr(), region.len()) {
+                Status::Ok => offset += region.len(),
+                status => return Err(status),
+            }
+        }
+        Ok(total)
+    }
+
+    /// fill the regions in order from successive area offsets, from the area
+    /// start, and return the total read length. The regions must fit
+    /// together in the area. With the strict-read feature, they also must
+    /// fit in the written data, see written_len().
+    pub fn scatter_read(&self, regions: &mut [&mut [u8]]) -> Result<usize, Status> {
+        let mut total = 0usize;
+        for region in regions.iter() {
+            total = match total.checked_add(region.len()) {
+                Some(total) if total <= N => total,
+                Some(total) => {
+                    return Err(Status::TooLarge.or_panic("copy from the area", 0, total))
+                }
+                None => {
+                    return Err(Status::Overflow.or_panic("copy from the area", 0, usize::MAX))
+                }
+            };
+        }
+        if self.check_written(total).is_err() {
+            return Err(Status::ShortRead.or_panic("copy from the area", 0, total));
+        }
+        let mut offset = 0;
+        for region in regions.iter_mut() {
+            match self.copy_vec_from_at(offset, region.as_mut_ptr(), region.len()) {
+                Status::Ok => offset += region.len(),
+                status => return Err(status),
+            }
+        }
+        Ok(total)
+    }
+
+    /// copy the src payload to the area, prefixed by its little-endian u16
+    /// length. The payload is written at offset 2. A payload longer than
+    /// u16::MAX or than the area remaining space is rejected with
+    /// Status::TooLarge.
+    pub fn write_length_prefixed(&self, src: &[u8]) -> Status {
+        let status = 'copy: {
+            let Ok(len) = u16::try_from(src.len()) else {
+                break 'copy Status::TooLarge;
+            };
+            if N < 2 || src.len() > N - 2 {
+                break 'copy Status::TooLarge;
+            }
+            match self.write_slice(&len.to_le_bytes()) {
+                Status::Ok => self.copy_vec_to_at(2, src.as_ptr(), src.len()),
+                status => status,
+            }
+        };
+        status.or_panic("copy to the area", 0, src.len())
+    }
+
+    /// read a payload prefixed by its little-endian u16 length to dst, and
+    /// return the payload length. The payload is read at offset 2. A length
+    /// bigger than the area remaining space or than dst is rejected with
+    /// Status::TooLarge.
+    pub fn read_length_prefixed(&self, dst: &mut [u8]) -> Result<usize, Status> {
+        let result = 'copy: {
+            let len = self.peek::<2>(0).map(u16::from_le_bytes)? as usize;
+            if len > N - 2 || len > dst.len() {
+                break 'copy Err(Status::TooLarge);
+            }
+            match self.copy_vec_from_at(2, dst.as_mut_ptr(), len) {
+                Status::Ok => Ok(len),
+                status => Err(status),
+            }
+        };
+        result.map_err(|status| status.or_panic("copy from the area", 0, dst.len()))
+    }
+
+    /// byte at the given area index, or None if the index is out of the area
+    pub fn get(&self, idx: usize) -> Option<u8> {
+        if !Self::in_area(idx) {
+            return None;
+        }
+        let mut byte = 0u8;
+        unsafe { self.area_read_at(idx, &mut byte, 1) }.ok()?;
+        Some(byte)
+    }
+
+    /// set the byte at the given area index. The written length is extended
+    /// up to the index, if needed. false is returned, and nothing is written,
+    /// if the index is out of the area.
+    pub fn set(&self, idx: usize, val: u8) -> bool {
+        if !Self::in_area(idx) {
+            return false;
+        }
+        unsafe { self.area_write_at(idx, &val, 1) };
+        true
+    }
+
+    /// read the L bytes at the given area offset
+    fn peek<const L: usize>(&self, offset: usize) -> Result<[u8; L], Status> {
+        let result = 'copy: {
+            const { assert!(L > 0) };
+            match offset.checked_add(L - 1) {
+                Some(last) if Self::in_area(last) => (),
+                Some(_) => break 'copy Err(Status::TooLarge),
+                None => break 'copy Err(Status::Overflow),
+            }
+            let mut bytes = [0u8; L];
+            if unsafe { self.area_read_at(offset, bytes.as_mut_ptr(), L) }.is_err() {
+                break 'copy Err(Status::ShortRead);
+            }
+            Ok(bytes)
+        };
+        result.map_err(|status| status.or_panic("copy from the area", offset, L))
+    }
+
+    /// read a ShmInfo from the area, and validate it, see ShmInfo::validate()
+    pub fn read_shm_info_validated(&self) -> Result<ShmInfo, ShmInfoError> {
+        let shminfo = self
+            .read::<ShmInfo>()
+            .map_err(|status| match status.into_result() {
+                Err(error) => ShmInfoError::Exchange(error),
+                Ok(()) => unreachable!(),
+            })?;
+        shminfo.validate()?;
+        Ok(shminfo)
+    }
+
+    /// copy an optional ShmInfo to the area, as a presence byte (0 or 1)
+    /// followed, when present, by the ShmInfo exchange area representation.
+    /// Test purpose only, as ShmInfo copies to the area.
+    #[cfg(any(test, feature = "test-helpers"))]
+    pub fn write_shm_info_opt(&self, shminfo: Option<&ShmInfo>) -> Status {
+        let status = 'copy: {
+            let Some(shminfo) = shminfo else {
+                break 'copy self.write_slice(&[0]);
+            };
+            let mut bytes = [0u8; ShmInfo::AREA_SIZE];
+            shminfo.put_le(&mut bytes);
+            if 1 + bytes.len() > N {
+                break 'copy Status::TooLarge;
+            }
+            match self.write_slice(&[1]) {
+                Status::Ok => self.copy_vec_to_at(1, bytes.as_ptr(), bytes.len()),
+                status => status,
+            }
+        };
+        status.or_panic("copy to the area", 0, 1 + ShmInfo::AREA_SIZE)
+    }
+
+    /// read an optional ShmInfo from the area, encoded as a presence byte
+    /// followed by the ShmInfo, see write_shm_info_opt(). A presence byte
+    /// other than 0 or 1 is rejected with Status::Invalid.
+    pub fn read_shm_info_opt(&self) -> Result<Option<ShmInfo>, Status> {
+        let result = 'copy: {
+            let mut presence = [0u8];
+            match self.copy_vec_from_at(0, presence.as_mut_ptr(), 1) {
+                Status::Ok => (),
+                status => break 'copy Err(status),
+            }
+            match presence[0] {
+                0 => break 'copy Ok(None),
+                1 => (),
+                _ => break 'copy Err(Status::Invalid),
+            }
+            let mut bytes = [0u8; ShmInfo::AREA_SIZE];
+            match self.copy_vec_from_at(1, bytes.as_mut_ptr(), bytes.len()) {
+                Status::Ok => Ok(Some(ShmInfo::get_le(&bytes))),
+                status => Err(status),
+            }
+        };
+        result.map_err(|status| status.or_panic("copy from the area", 0, 1 + ShmInfo::AREA_SIZE))
+    }
+
+    /// copy a H-typed header from the area start, and the body following
+    /// it, in one call. The body length is the body slice length.
+    ///
+    /// The body is read at offset size_of::<H>(). Both regions are checked
+    /// before any copy, so that on failure neither the header nor the body
+    /// is modified.
+    pub fn copy_split_from<H>(&self, header: &mut H, body: &mut [u8]) -> Status
+    where
+        Self: ExchangeArea<H>,
+    {
+        let offset = core::mem::size_of::<H>();
+        let body_len = body.len();
+        let status = 'copy: {
+            let end = match offset.checked_add(body_len) {
+                Some(end) if end <= N => end,
+                Some(_) => break 'copy Status::TooLarge,
+                None => break 'copy Status::Overflow,
+            };
+            if self.check_overlapping(body.as_ptr(), body_len).is_err() {
+                break 'copy Status::Overlap;
+            }
+            if self.check_written(end).is_err() {
+                break 'copy Status::ShortRead;
+            }
+            match self.copy_from(header) {
+                Status::Ok => (),
+                status => break 'copy status,
+            }
+            <Self as ExchangeArea<u8>>::copy_vec_from_at(self, offset, body.as_mut_ptr(), body_len)
+        };
+        status.or_panic("copy from the area", 0, offset.saturating_add(body_len))
+    }
+
+    /// iterate over the count first ShmInfo of a packed ShmInfo array in the
+    /// area. The iteration is bounded by the number of ShmInfo that fit in the
+    /// area. Each descriptor is read from the area when the iterator yields it.
+    /// With the strict-read feature, the iteration stops at the first
+    /// descriptor beyond the written data.
+    pub fn shm_info_iter(&self, count: usize) -> impl Iterator<Item = ShmInfo> + '_ {
+        let size = core::mem::size_of::<ShmInfo>();
+        let count = count.min(<Self as ExchangeArea<ShmInfo>>::area_capacity(self));
+        (0..count).map_while(move |i| {
+            let mut bytes = [0u8; ShmInfo::AREA_SIZE];
+            unsafe { self.area_read_at(i * size, bytes.as_mut_ptr(), bytes.len()) }.ok()?;
+            Some(ShmInfo::get_le(&bytes))
+        })
+    }
+
+    /// copy the area first len bytes to an owned vector, for host side
+    /// inspection. len is bounded to the area length.
+    #[cfg(feature = "std")]
+    pub fn to_vec(&self, len: usize) -> std::vec::Vec<u8> {
+        let len = len.min(N);
+        let mut bytes = std::vec![0u8; len];
+        unsafe { self.area_read_raw(0, bytes.as_mut_ptr(), len) };
+        bytes
+    }
+
+    /// copy the whole area content out, to be restored later with restore()
+    /// after another exchange
+    pub fn snapshot(&self) -> [u8; N] {
+        let mut snap = [0u8; N];
+        unsafe { self.area_read_raw(0, snap.as_mut_ptr(), N) };
+        snap
+    }
+
+    /// copy back a snapshot taken with snapshot() to the whole area. The
+    /// whole area is then accounted as written.
+    pub fn restore(&self, snap: &[u8; N]) -> Status {
+        self.write_slice(snap)
+    }
+
+    /// shared view of the area first len bytes, for read-only inspection
+    /// without copy. len must not be bigger than the area length.
+    ///
+    /// # Safety
+    ///
+    /// The area must not be written while the returned slice is alive, neither
+    /// by a copy to the area nor by the kernel during a syscall, as the slice
+    /// content is assumed to be immutable.
+    pub unsafe fn as_bytes(&self, len: usize) -> Result<&[u8], Status> {
+        if len > N {
+            return Err(Status::TooLarge);
+        }
+        Ok(core::slice::from_raw_parts(self.area_ptr(), len))
+    }
+
+    /// mutable view of the area first len bytes, to build a payload in place
+    /// without copy. len must not be bigger than the area length. The len
+    /// bytes are accounted as written, see written_len().
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is a mutable reference to the global exchange area,
+    /// so it must be the only access to the area while it is alive: no other
+    /// view, no copy to or from the area through any Area handle, and no
+    /// syscall. The slice must be dropped, and sync() called, before the
+    /// syscall is issued.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_bytes_mut(&self, len: usize) -> Result<&mut [u8], Status> {
+        if len > N {
+            return Err(Status::TooLarge);
+        }
+        self.valid_len.set(len);
+        Ok(core::slice::from_raw_parts_mut(self.area_ptr(), len))
+    }
+
+    /// format the area first len bytes to out, as rows of 16 bytes giving the
+    /// offset, the bytes in hex and their ASCII representation. len is bounded
+    /// to the area length.
+    ///
+    /// ```text
+    /// 0000  73 76 63 20 65 78 63 68 61 6e 67 65 00 ff              |svc exchange..|
+    /// ```
+    pub fn hexdump(&self, len: usize, out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+        const ROW_LEN: usize = 16;
+        let len = len.min(N);
+        let area = self.area_ptr();
+        let mut row = [0u8; ROW_LEN];
+        for offset in (0..len).step_by(ROW_LEN) {
+            let row = &mut row[..ROW_LEN.min(len - offset)];
+            for (i, byte) in row.iter_mut().enumerate() {
+                *byte = unsafe { area.add(offset + i).read_volatile() };
+            }
+            write!(out, "{:04x} ", offset)?;
+            for byte in row.iter() {
+                write!(out, " {:02x}", byte)?;
+            }
+            for _ in row.len()..ROW_LEN {
+                out.write_str("   ")?;
+            }
+            out.write_str("  |")?;
+            for byte in row.iter() {
+                let c = if byte.is_ascii_graphic() || *byte == b' ' { *byte as char } else { '.' };
+                out.write_char(c)?;
+            }
+            out.write_str("|\n")?;
+        }
+        Ok(())
+    }
+
+    /// compare the area first bytes with the expected slice, without copying
+    /// them out of the area. An expected slice bigger than the area length
+    /// never matches.
+    ///
+    /// All the bytes are compared, whatever the first difference position
+    /// is, so that the comparison duration does not depend on the content.
+    pub fn matches(&self, expected: &[u8]) -> bool {
+        if expected.len() > N {
+            return false;
+        }
+        let area = self.area_ptr();
+        let mut diff = 0u8;
+        for (i, byte) in expected.iter().enumerate() {
+            diff |= unsafe { area.add(i).read_volatile() } ^ byte;
+        }
+        diff == 0
+    }
+
+    /// compare the area first len bytes with the other slice first len bytes.
+    /// A len bigger than the area length or than the other slice length
+    /// never compares equal.
+    ///
+    /// Area does not implement PartialEq<[u8]>: all the handles share the
+    /// same global storage, so the comparison is with the current storage
+    /// content, not with a value owned by the handle. This is mainly meant
+    /// for tests and debug assertions.
+    pub fn content_eq(&self, other: &[u8], len: usize) -> bool {
+        match other.get(..len) {
+            Some(expected) => self.matches(expected),
+            None => false,
+        }
+    }
+
+    /// CRC-32 (IEEE 802.3) of the area first len bytes. len must not be
+    /// bigger than the area length.
+    ///
+    /// This is a diagnostic helper: comparing the checksum before and after
+    /// a syscall detects a kernel-side modification of the area content.
+    pub fn checksum(&self, len: usize) -> Result<u32, Status> {
+        if len > N {
+            return Err(Status::TooLarge);
+        }
+        let area = self.area_ptr();
+        let mut crc = !0u32;
+        for i in 0..len {
+            crc ^= unsafe { area.add(i).read_volatile() } as u32;
+            for _ in 0..8 {
+                crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+            }
+        }
+        Ok(!crc)
+    }
+
+    /// forget the written data, so that the next append() starts at the area
+    /// start
+    ///
+    /// Contrary to clear(), the area content is left untouched: only the
+    /// written length is reset. Use clear() to scrub sensitive data.
+    pub fn reset(&self) {
+        self.valid_len.set(0);
+    }
+
+    /// zero the whole exchange area
+    ///
+    /// Previous exchanges content, that may be sensitive, is scrubbed from the
+    /// area. Volatile writes are used so that the compiler can't elide them.
+    /// The written length is reset, as with reset().
+    pub fn clear(&self) {
+        self.fill(0);
+    }
+
+    /// write byte across the whole exchange area
+    ///
+    /// Filling the area with a sentinel before a syscall detects the bytes
+    /// the kernel did not write. Volatile writes are used, and the written
+    /// length is reset, as with clear().
+    pub fn fill(&self, byte: u8) {
+        unsafe {
+            let area = self.area_ptr();
+            for i in 0..self.area_len() {
+                area.add(i).write_volatile(byte);
+            }
+        }
+        self.valid_len.set(0);
+    }
+
+    /// check that the len bytes long buffer at ptr does not overlap the
+    /// exchange area, so that it can be given to the unchecked copies. A
+    /// buffer wrapping around the address space is never disjoint.
+    pub fn is_disjoint(&self, ptr: *const u8, len: usize) -> bool {
+        self.check_overlapping(ptr, len).is_ok()
+    }
+
+    /// exchange area storage base address, to check the area placement in
+    /// the memory map. With the double-buffer feature, this is the first
+    /// slot address. For caller-provided storage, this is the storage base
+    /// address.
+    pub fn base_addr(&self) -> usize {
+        if let Some(base) = self.base {
+            return base.as_ptr() as usize;
+        }
+        EXCHANGE_AREA.as_ptr() as usize
+    }
+
+    /// give the slot prepared by the job to the kernel
+    ///
+    /// With the double-buffer feature, the exchange storage holds two slots.
+    /// The kernel accesses the active slot, while the job copies target the
+    /// other one, so that the next payload can be prepared without clobbering
+    /// the current one. swap() makes the prepared slot active, and the job
+    /// copies then target the previously active slot. Swapping again after
+    /// the syscall gives back the slot, and the kernel response, to the job.
+    ///
+    /// The written length is not tracked per slot, and is still the length
+    /// of the last copy to the area. Areas on caller-provided storage, see
+    /// from_raw_parts(), have a single slot and are not affected.
+    #[cfg(feature = "double-buffer")]
+    pub fn swap(&self) {
+        ACTIVE_SLOT.fetch_xor(1, Ordering::Relaxed);
+    }
+
+    /// index of the slot given to the kernel, see swap()
+    #[cfg(feature = "double-buffer")]
+    pub fn active_slot(&self) -> usize {
+        ACTIVE_SLOT.load(Ordering::Relaxed)
+    }
+
+    /// make the area writes visible before issuing a syscall
+    ///
+    /// The syscall trap instruction is not a memory access, so nothing
+    /// prevents the previous area writes to be reordered after it. This method
+    /// must be called between the last area write and the syscall. On Arm
+    /// targets, a data memory barrier is emitted. Elsewhere (host test builds)
+    /// only the compiler is prevented from reordering the accesses.
+    ///
+    /// ```
+    /// use svcexch::{Area, ExchangeArea, Status};
+    ///
+    /// fn log(area: &Area, msg: &str) {
+    ///     if let Status::Ok = area.copy_vec_to(msg.as_ptr(), msg.len()) {
+    ///         area.sync();
+    ///         // the log syscall can now be issued
+    ///     }
+    /// }
+    /// ```
+    pub fn sync(&self) {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+        #[cfg(target_arch = "arm")]
+        unsafe {
+            core::arch::asm!("dmb sy", options(nostack, preserves_flags));
+        }
+    }
+
+    /// start writing an exchange payload, see ExchangeGuard
+    pub fn begin_exchange(&self) -> ExchangeGuard<'_, N> {
+        ExchangeGuard { area: self }
+    }
+}
+
+/// Scoped exchange payload writer
+///
+/// The guard gives access to the area methods, and calls Area::sync() when
+/// dropped. Dropping the guard is the commit point of the payload: the
+/// syscall can be issued right after.
+///
+/// ```
+/// use svcexch::{Area, Status};
+///
+/// fn log(area: &Area, msg: &str) -> Status {
+///     let status = {
+///         let exchange = area.begin_exchange();
+///         exchange.write_str(msg)
+///     };
+///     // the log syscall can now be issued
+///     status
+/// }
+/// ```
+pub struct ExchangeGuard<'a, const N: usize = EXCHANGE_AREA_LEN> {
+    area: &'a Area<N>,
+}
+
+impl<const N: usize> core::ops::Deref for ExchangeGuard<'_, N> {
+    type Target = Area<N>;
+
+    fn deref(&self) -> &Area<N> {
+        self.area
+    }
+}
+
+impl<const N: usize> Drop for ExchangeGuard<'_, N> {
+    fn drop(&mut self) {
+        self.area.sync();
+        #[cfg(test)]
+        tests::SYNCS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Formatted text writer to the area
+///
+/// Formatted bytes are buffered and flushed to the area in area length
+/// sized chunks. Once a chunk is copied to the area, the flush callback is
+/// called with the chunk length, so that the caller issues the print syscall
+/// before the area is overwritten by the next chunk.
+///
+/// The last, incomplete, chunk is only flushed by an explicit call to
+/// flush().
+///
+/// ```
+/// use core::fmt::Write;
+/// use svcexch::{Area, AreaWriter, Status};
+///
+/// fn log(area: &Area, value: u32) -> Status {
+///     let mut writer = AreaWriter::new(area, |_len| {
+///         // the log syscall can now be issued for len bytes
+///     });
+///     let _ = write!(writer, "value: {}", value);
+///     writer.flush()
+/// }
+/// ```
+pub struct AreaWriter<'a, F, const N: usize = EXCHANGE_AREA_LEN>
+where
+    F: FnMut(usize),
+{
+    area: &'a Area<N>,
+    buffer: [u8; N],
+    pos: usize,
+    on_flush: F,
+}
+
+impl<'a, F, const N: usize> AreaWriter<'a, F, N>
+where
+    F: FnMut(usize),
+{
+    /// create a new writer to the given area. on_flush is called with the
+    /// chunk length each time a chunk has been copied to the area.
+    pub fn new(area: &'a Area<N>, on_flush: F) -> Self {
+        Self {
+            area,
+            buffer: [0u8; N],
+            pos: 0,
+            on_flush,
+        }
+    }
+
+    /// copy the buffered bytes to the area and call the flush callback.
+    /// Nothing is done if no byte is buffered.
+    pub fn flush(&mut self) -> Status {
+        if self.pos == 0 {
+            return Status::Ok;
+        }
+        match self.area.write_slice(&self.buffer[..self.pos]) {
+            Status::Ok => (),
+            status => return status,
+        }
+        self.area.sync();
+        (self.on_flush)(self.pos);
+        self.pos = 0;
+        Status::Ok
+    }
+}
+
+impl<F, const N: usize> core::fmt::Write for AreaWriter<'_, F, N>
+where
+    F: FnMut(usize),
+{
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let mut bytes = s.as_bytes();
+        while !bytes.is_empty() {
+            if self.pos == N {
+                if !matches!(self.flush(), Status::Ok) {
+                    return Err(core::fmt::Error);
+                }
+                // a zero length area can't hold any byte, even once flushed
+                if self.pos == N {
+                    return Err(core::fmt::Error);
+                }
+            }
+            let len = bytes.len().min(N - self.pos);
+            self.buffer[self.pos..self.pos + len].copy_from_slice(&bytes[..len]);
+            self.pos += len;
+            bytes = &bytes[len..];
+        }
+        Ok(())
+    }
+}
+
+/// Sequential accesses to the area
+///
+/// The cursor keeps track of the current byte offset in the area, so that
+/// composite payloads can be written, or read back, field by field. Each
+/// access advances the cursor by the accessed length. Accesses that do not
+/// fit in the area remaining space are rejected and do not move the cursor.
+///
+/// Words are stored in little-endian byte order, as for ExchangeArea<u32>.
+pub struct AreaCursor<'a, const N: usize = EXCHANGE_AREA_LEN> {
+    area: &'a Area<N>,
+    pos: usize,
+}
+
+impl<'a, const N: usize> AreaCursor<'a, N> {
+    /// create a new cursor at the start of the given area
+    pub fn new(area: &'a Area<N>) -> Self {
+        Self { area, pos: 0 }
+    }
+
+    /// number of bytes between the cursor and the area end
+    pub fn remaining(&self) -> usize {
+        N - self.pos
+    }
+
+    /// write the whole src slice at the cursor position
+    pub fn put_slice(&mut self, src: &[u8]) -> Status {
+        let status = self.area.copy_vec_to_at(self.pos, src.as_ptr(), src.len());
+        if matches!(status, Status::Ok) {
+            self.pos += src.len();
+        }
+        status
+    }
+
+    /// write a single byte at the cursor position
+    pub fn put_u8(&mut self, value: u8) -> Status {
+        self.put_slice(&[value])
+    }
+
+    /// write a little-endian u32 at the cursor position
+    pub fn put_u32_le(&mut self, value: u32) -> Status {
+        self.put_slice(&value.to_le_bytes())
+    }
+
+    /// fill the whole dst slice from the cursor position
+    pub fn get_slice(&mut self, dst: &mut [u8]) -> Status {
+        let status = self.area.copy_vec_from_at(self.pos, dst.as_mut_ptr(), dst.len());
+        if matches!(status, Status::Ok) {
+            self.pos += dst.len();
+        }
+        status
+    }
+
+    /// read a single byte from the cursor position
+    pub fn get_u8(&mut self) -> Result<u8, Status> {
+        let mut bytes = [0u8; 1];
+        match self.get_slice(&mut bytes) {
+            Status::Ok => Ok(bytes[0]),
+            status => Err(status),
+        }
+    }
+
+    /// read a little-endian u32 from the cursor position
+    pub fn get_u32_le(&mut self) -> Result<u32, Status> {
+        let mut bytes = [0u8; core::mem::size_of::<u32>()];
+        match self.get_slice(&mut bytes) {
+            Status::Ok => Ok(u32::from_le_bytes(bytes)),
+            status => Err(status),
+        }
+    }
+}
+
+/// T-typed field of a payload layout, see exchange_layout!
+///
+/// The field value is stored at the field offset in little-endian byte
+/// order, as with Area::write_vec(), so the field type must be a Pod type.
+pub struct LayoutField<T: Pod> {
+    offset: usize,
+    _type: core::marker::PhantomData<T>,
+}
+
+impl<T: Pod> LayoutField<T> {
+    /// create a field at the given offset in the area
+    pub const fn new(offset: usize) -> Self {
+        Self {
+            offset,
+            _type: core::marker::PhantomData,
+        }
+    }
+
+    /// field offset in the area, in bytes
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// field end offset in the area, in bytes
+    pub const fn end(&self) -> usize {
+        self.offset + core::mem::size_of::<T>()
+    }
+
+    /// copy the value to the field. The written length is extended up to
+    /// the field end, if needed. The field must fit in the area length.
+    pub fn write<const N: usize>(&self, area: &Area<N>, value: &T) -> Status {
+        let status = 'copy: {
+            let size = core::mem::size_of::<T>();
+            if self.end() > N {
+                break 'copy Status::TooLarge;
+            }
+            let from = value as *const T as *const u8;
+            if area.check_overlapping(from, size).is_err() {
+                break 'copy Status::Overlap;
+            }
+            let value = value.to_le();
+            unsafe { area.area_write_at(self.offset, &value as *const T as *const u8, size) };
+            Status::Ok
+        };
+        status.or_panic("copy to the area", self.offset, core::mem::size_of::<T>())
+    }
+
+    /// read the field value. The field must fit in the area length.
+    pub fn read<const N: usize>(&self, area: &Area<N>) -> Result<T, Status> {
+        let size = core::mem::size_of::<T>();
+        let result = 'copy: {
+            if self.end() > N {
+                break 'copy Err(Status::TooLarge);
+            }
+            let mut value = core::mem::MaybeUninit::<T>::uninit();
+            unsafe {
+                if area.area_read_at(self.offset, value.as_mut_ptr() as *mut u8, size).is_err() {
+                    break 'copy Err(Status::ShortRead);
+                }
+                // any bit pattern is a valid Pod value
+                Ok(T::from_le(value.assume_init()))
+            }
+        };
+        result.map_err(|status| status.or_panic("copy from the area", self.offset, size))
+    }
+}
+
+/// Declare a payload layout, with its fields packed back to back
+///
+/// The layout type gets a LayoutField associated const per field, named
+/// after the field, and a SIZE const giving the layout total size. The
+/// layout is statically checked to fit in the exchange area. The fields
+/// types must be Pod types.
+///
+/// ```
+/// use svcexch::{exchange_layout, Area, Status};
+///
+/// exchange_layout! {
+///     struct Req {
+///         header: u32,
+///         body: [u8; 32],
+///     }
+/// }
+///
+/// let area = Area::take().unwrap();
+/// assert_eq!(Req::body.offset(), 4);
+/// assert!(matches!(Req::header.write(&area, &0x42), Status::Ok));
+/// assert!(matches!(Req::header.read(&area), Ok(0x42)));
+/// ```
+#[macro_export]
+macro_rules! exchange_layout {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident { $($field:ident: $t:ty),* $(,)? }) => {
+        $(#[$meta])*
+        $vis struct $name;
+
+        #[allow(non_upper_case_globals)]
+        impl $name {
+            $crate::exchange_layout!(@fields 0usize; $($field: $t,)*);
+
+            /// layout total size, in bytes
+            pub const SIZE: usize = 0 $(+ core::mem::size_of::<$t>())*;
+        }
+
+        const _: () = assert!($name::SIZE <= $crate::EXCHANGE_AREA_LEN);
+    };
+    (@fields $offset:expr; $field:ident: $t:ty, $($rest:tt)*) => {
+        pub const $field: $crate::LayoutField<$t> = $crate::LayoutField::new($offset);
+        $crate::exchange_layout!(@fields $offset + core::mem::size_of::<$t>(); $($rest)*);
+    };
+    (@fields $offset:expr;) => {};
+}
+
+/// copy len bytes from src to the exchange area, for the uapi C glue
+///
+/// Returns 0 on success, or a negative value giving the failure reason, see
+/// the Status to i32 conversion.
+///
+/// The failure is always returned, even with the panic-on-error feature, as a
+/// panic can't unwind across the C boundary.
+///
+/// # Safety
+///
+/// src must be valid for reads of len bytes, and the exchange area must not
+/// be accessed concurrently, e.g. from another thread.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn svcexch_copy_to(src: *const u8, len: usize) -> i32 {
+    Area::new().vec_to_counted(src, len).0.into()
+}
+
+/// copy len bytes from the exchange area to dst, for the uapi C glue
+///
+/// Returns 0 on success, or a negative value giving the failure reason, see
+/// the Status to i32 conversion.
+///
+/// The C glue does not track the data written to the area, so the whole area
+/// is considered written for the strict-read feature. As for
+/// svcexch_copy_to(), the failure is returned even with the panic-on-error
+/// feature.
+///
+/// # Safety
+///
+/// dst must be valid for writes of len bytes, and the exchange area must not
+/// be accessed concurrently, e.g. from another thread.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn svcexch_copy_from(dst: *mut u8, len: usize) -> i32 {
+    let area: Area = Area {
+        valid_len: Cell::new(EXCHANGE_AREA_LEN),
+        owner: false,
+        base: None,
+    };
+    area.vec_from_counted(dst, len).0.into()
+}
+
+/// Release the exchange area ownership when the owning Area handle is dropped
+///
+/// With the zeroize-on-drop feature, the whole exchange area storage, all
+/// the slots included, is zeroized when the last handle sharing the
+/// ownership, see Area::take(), is dropped. Dropping a clone while the
+/// others are still alive, or a handle created with new() or
+/// from_raw_parts(), does not wipe anything.
+impl<const N: usize> Drop for Area<N> {
+    fn drop(&mut self) {
+        if self.owner {
+            // the handles sharing the ownership are all on this thread, see
+            // AreaStorage, so the count can't change until the release
+            #[cfg(feature = "zeroize-on-drop")]
+            if TAKEN.load(Ordering::Relaxed) == 1 {
+                EXCHANGE_AREA.zeroize();
+            }
+            TAKEN.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+/// Non-trait relative utility functions implementation for Area
+///
+/// Here are defined local functions only, used as helper for trait methods
+/// implementations.
+impl Area {
+
+    /// create a new Area object. No valid data is tracked in the area until
+    /// the first copy to it.
+    ///
+    /// This is a const fn, so that a handle can be built in a static
+    /// initializer. Use take() to get a handle checked for ownership.
+    ///
+    /// # Safety
+    ///
+    /// The handle is not accounted in the exchange area ownership: the
+    /// exchange area must not be accessed through any other handle, including
+    /// one returned by take(), while this one is used, and the handle must
+    /// not be used concurrently from several threads.
+    pub const unsafe fn new() -> Self {
+        Self::new_sized()
+    }
+
+    /// take the exchange area ownership
+    ///
+    /// The exchange area is a single global zone, so two handles used
+    /// concurrently may clobber each other in-flight data. Only one handle
+    /// returned by take() or take_sized() can be alive at a time: None is
+    /// returned until this handle, and all its clones, are dropped.
+    ///
+    /// This, and Default, are the only safe ways to get an Area bound to the
+    /// exchange area. new() is unsafe as it bypasses the ownership.
+    pub fn take() -> Option<Self> {
+        Self::take_sized()
+    }
+
+    /// register the hook called on each copy to or from the exchange area,
+    /// with the operation and the copied length in bytes. None unregisters
+    /// the current hook.
+    ///
+    /// The hook is called by the copy itself, so it must not access the
+    /// exchange area.
+    #[cfg(feature = "trace")]
+    pub fn set_trace_hook(hook: Option<fn(ExchangeOp, usize)>) {
+        let hook = match hook {
+            Some(hook) => hook as *mut (),
+            None => core::ptr::null_mut(),
+        };
+        TRACE_HOOK.store(hook, Ordering::Release);
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for Area<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Area")
+            .field("length", &N)
+            .field("valid_len", &self.valid_len.get())
+            .finish()
+    }
+}
+
+/// A clone of the handle returned by Area::take() shares its ownership: the
+/// exchange area is only released once all of them are dropped
+impl<const N: usize> Clone for Area<N> {
+    fn clone(&self) -> Self {
+        if self.owner {
+            TAKEN.fetch_add(1, Ordering::Relaxed);
+        }
+        Self {
+            valid_len: self.valid_len.clone(),
+            owner: self.owner,
+            base: self.base,
+        }
+    }
+}
+
+/// The default handle is taken with Area::take_sized(), as new() is unsafe.
+///
+/// # Panics
+///
+/// Panics if the exchange area is already taken.
+impl<const N: usize> Default for Area<N> {
+    fn default() -> Self {
+        Self::take_sized().expect("exchange area already taken")
+    }
+}
+
+impl<const N: usize> Area<N> {
+
+    /// create a new Area object for a N bytes long exchange zone.
+    ///
+    /// # Safety
+    ///
+    /// Same as Area::new(). Use take_sized() to get a handle checked for
+    /// ownership.
+    pub const unsafe fn new_sized() -> Self {
+        const { assert!(N <= EXCHANGE_AREA_LEN) };
+        Self {
+            valid_len: Cell::new(0),
+            owner: false,
+            base: None,
+        }
+    }
+
+    /// create an Area object bound to the len bytes long caller-provided
+    /// storage at base, instead of the linked exchange area. The copies use
+    /// the storage first N bytes. len must not be smaller than N, and base
+    /// must be word aligned.
+    ///
+    /// # Safety
+    ///
+    /// base must be valid for reads and writes of len bytes while the
+    /// returned Area is alive, and the storage must only be accessed through
+    /// this Area and its clones.
+    pub unsafe fn from_raw_parts(base: *mut u8, len: usize) -> Result<Self, Status> {
+        let Some(base) = NonNull::new(base) else {
+            return Err(Status::NullPointer);
+        };
+        if !(base.as_ptr() as usize).is_multiple_of(core::mem::align_of::<usize>()) {
+            return Err(Status::Misaligned);
+        }
+        if len < N {
+            return Err(Status::TooLarge);
+        }
+        Ok(Self {
+            valid_len: Cell::new(0),
+            owner: false,
+            base: Some(base),
+        })
+    }
+
+    /// take the exchange area ownership, for a N bytes long exchange zone.
+    /// See Area::take().
+    pub fn take_sized() -> Option<Self> {
+        if TAKEN.compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            return None;
+        }
+        // SAFETY: the ownership has just been taken, no other handle is alive
+        let mut area = unsafe { Self::new_sized() };
+        area.owner = true;
+        Some(area)
+    }
+
+    /// number of valid bytes in the area, as written by the last copy to the
+    /// area. Data written by the kernel are not accounted.
+    pub fn written_len(&self) -> usize {
+        self.valid_len.get()
+    }
+
+    /// number of free bytes in the area after the written data, see
+    /// written_len()
+    pub fn remaining(&self) -> usize {
+        N - self.valid_len.get()
+    }
+
+    /// copy length bytes from the user buffer to the exchange area
+    ///
+    /// The kernel reads the exchange area once the syscall is issued, behind
+    /// the back of the compiler. As the job never reads back these bytes, a
+    /// plain copy_nonoverlapping() to the area could be reordered or even
+    /// elided by the optimizer. Volatile writes can't be.
+    ///
+    /// The written length is recorded as the area valid length.
+    #[inline]
+    unsafe fn area_write(&self, from: *const u8, length: usize) {
+        self.area_copy_to(0, from, length);
+        self.valid_len.set(length);
+    }
+
+    /// same as area_write(), starting at the given offset in the area
+    ///
+    /// The data written at an offset are appended to the area valid data, so
+    /// the valid length is extended up to the end of the written data, if
+    /// needed.
+    #[inline]
+    unsafe fn area_write_at(&self, offset: usize, from: *const u8, length: usize) {
+        self.area_copy_to(offset, from, length);
+        self.valid_len.set(self.valid_len.get().max(offset + length));
+    }
+
+    /// volatile copy of length bytes to the area, starting at offset
+    ///
+    /// Word-sized accesses are used when both the offset and the length are
+    /// word multiples, the area start being word aligned. The user buffer
+    /// may be unaligned.
+    #[inline]
+    unsafe fn area_copy_to(&self, offset: usize, from: *const u8, length: usize) {
+        const WORD: usize = core::mem::size_of::<usize>();
+        #[cfg(feature = "trace")]
+        self.trace(ExchangeOp::Write, length);
+        let area = self.area_ptr().add(offset);
+        if offset.is_multiple_of(WORD) && length.is_multiple_of(WORD) {
+            let area = area as *mut usize;
+            let from = from as *const usize;
+            for i in 0..length / WORD {
+                area.add(i).write_volatile(from.add(i).read_unaligned());
+            }
+        } else {
+            for i in 0..length {
+                area.add(i).write_volatile(from.add(i).read());
+            }
+        }
+    }
+
+    /// copy length bytes from the exchange area to the user buffer
+    ///
+    /// The exchange area content is written by the kernel during the syscall,
+    /// so the compiler must not assume it knows it. Volatile reads are used
+    /// for the same reason as in area_write().
+    ///
+    /// All the reads from the area go through this method or area_read_at(),
+    /// so that the strict-read check is made in a single place: with the
+    /// strict-read feature, nothing is read and Err is returned if the read
+    /// range goes beyond the written data, see check_written().
+    #[inline]
+    unsafe fn area_read(&self, to: *mut u8, length: usize) -> Result<(), ()> {
+        self.area_read_at(0, to, length)
+    }
+
+    /// same as area_read(), starting at the given offset in the area
+    #[inline]
+    unsafe fn area_read_at(&self, offset: usize, to: *mut u8, length: usize) -> Result<(), ()> {
+        self.check_written(offset + length)?;
+        self.area_read_raw(offset, to, length);
+        Ok(())
+    }
+
+    /// same as area_read_at(), without the strict-read check, for whole area
+    /// inspection
+    ///
+    /// Word-sized accesses are used in the same conditions as in
+    /// area_copy_to().
+    #[inline]
+    unsafe fn area_read_raw(&self, offset: usize, to: *mut u8, length: usize) {
+        const WORD: usize = core::mem::size_of::<usize>();
+        #[cfg(feature = "trace")]
+        self.trace(ExchangeOp::Read, length);
+        let area = self.area_ptr().add(offset);
+        if offset.is_multiple_of(WORD) && length.is_multiple_of(WORD) {
+            let area = area as *const usize;
+            let to = to as *mut usize;
+            for i in 0..length / WORD {
+                to.add(i).write_unaligned(area.add(i).read_volatile());
+            }
+        } else {
+            for i in 0..length {
+                to.add(i).write(area.add(i).read_volatile());
+            }
+        }
+    }
+
+    /// call the registered trace hook, if any
+    #[cfg(feature = "trace")]
+    fn trace(&self, op: ExchangeOp, length: usize) {
+        let hook = TRACE_HOOK.load(Ordering::Acquire);
+        if !hook.is_null() {
+            // only fn(ExchangeOp, usize) pointers are stored by set_trace_hook()
+            let hook = unsafe { core::mem::transmute::<*mut (), fn(ExchangeOp, usize)>(hook) };
+            hook(op, length);
+        }
+    }
+
+    /// check that length bytes have been written to the area by the last copy
+    ///
+    /// This is only enforced with the strict-read feature, by area_read_at().
+    /// Otherwise, reading stale data beyond the written ones is allowed.
+    fn check_written(&self, length: usize) -> Result<(), ()> {
+        if cfg!(feature = "strict-read") && length > self.valid_len.get() {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// check that the given pointer is aligned for T
+    ///
+    /// Byte copies do not require it, but typed accesses to the user buffer
+    /// do.
+    fn check_alignment<T>(pointer: *const T) -> Result<(), ()> {
+        if pointer.is_aligned() {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// exchange area base address
+    ///
+    /// All the area accesses and checks are made relative to this address.
+    /// With the double-buffer feature, this is the base address of the slot
+    /// that is not active. For caller-provided storage, this is the storage
+    /// base address.
+    #[inline]
+    fn area_ptr(&self) -> *mut u8 {
+        if let Some(base) = self.base {
+            return base.as_ptr();
+        }
+        #[cfg(feature = "double-buffer")]
+        {
+            let slot = 1 - ACTIVE_SLOT.load(Ordering::Relaxed);
+            EXCHANGE_AREA.as_ptr().wrapping_add(slot * EXCHANGE_AREA_LEN)
+        }
+        #[cfg(not(feature = "double-buffer"))]
+        EXCHANGE_AREA.as_ptr()
+    }
+
+    /// exchange zone length, in bytes
+    const fn area_len(&self) -> usize {
+        N
+    }
+
+    /// true if the zone length is a power of two, see in_area()
+    const LEN_IS_POW2: bool = N.is_power_of_two();
+
+    /// check that the given index is inside the exchange zone
+    ///
+    /// When the zone length is a power of two, as the default
+    /// EXCHANGE_AREA_LEN, the check is a mask of the index high bits. Other
+    /// lengths, including a configured EXCHANGE_AREA_LEN that is not a power
+    /// of two, fall back to the generic comparison, so the check is correct
+    /// for any N. LEN_IS_POW2 is a constant, so the branch is resolved at
+    /// build time.
+    ///
+    /// All the indexed accessors (get(), set() and the peek_*() methods)
+    /// use this check.
+    #[inline]
+    const fn in_area(idx: usize) -> bool {
+        if Self::LEN_IS_POW2 {
+            idx & !(N - 1) == 0
+        } else {
+            idx < N
+        }
+    }
+
+    /// check that the given vector do not overlap with the exchange area
+    ///
+    /// This is required as the area copies assume that the user buffer and
+    /// the exchange area never alias.
+    /// With the double-buffer feature, the buffer must not overlap any slot.
+    #[inline]
+    pub(crate) fn check_overlapping(&self, pointer: *const u8, length: usize) -> Result<(), ()> {
+        if self.base.is_some() {
+            return Self::check_overlapping_in(self.area_ptr(), N, pointer, length);
+        }
+        #[cfg(feature = "double-buffer")]
+        let (area, area_len) = (EXCHANGE_AREA.as_ptr(), EXCHANGE_AREA_LEN * AREA_SLOTS);
+        #[cfg(not(feature = "double-buffer"))]
+        let (area, area_len) = (self.area_ptr(), self.area_len());
+        Self::check_overlapping_in(area, area_len, pointer, length)
+    }
+
+    /// check that the given vector do not overlap with the area_len bytes
+    /// long area starting at area
+    ///
+    /// Both the area and the buffer are half-open ranges: their end address
+    /// is one-past-the-end, so a buffer immediately adjacent to the area is
+    /// accepted.
+    ///
+    /// The check is made on integer addresses, as pointer arithmetic on a
+    /// bogus buffer would be undefined behavior. A buffer wrapping around
+    /// the address space is rejected.
+    pub(crate) fn check_overlapping_in(
+        area: *const u8,
+        area_len: usize,
+        pointer: *const u8,
+        length: usize,
+    ) -> Result<(), ()> {
+        let area = area as usize;
+        let area_end = area.checked_add(area_len).ok_or(())?;
+        let pointer = pointer as usize;
+
+        // buffer wraps around the address space, abort
+        let buffer_end = pointer.checked_add(length).ok_or(())?;
+
+        // buffer starts in the middle of the exchange area, abort
+        if pointer >= area && pointer < area_end {
+            return Err(());
+        }
+
+        // buffer ends in the exchange area, abort
+        // Note: this is unlikely to happen if `svc_exchange` is always assumed to be at
+        // the beginning of RAM
+        if buffer_end > area && buffer_end <= area_end {
+            return Err(());
+        }
+
+        // exchange area is contained within the buffer, abort
+        if pointer <= area && buffer_end >= area_end {
+            return Err(());
+        }
+        Ok(())
+    }
+}
+
+/// In-memory exchange area, for upper layer tests
+///
+/// The mock owns its N bytes long storage instead of using the global
+/// exchange area, so that tests of components generic over ExchangeArea<u8>
+/// do not depend on the linked zone. The writes are recorded and the
+/// content can be inspected with contents(), while load() sets the data
+/// replayed by the next reads, e.g. a kernel response. As for Area, the
+/// failed copies panic with the panic-on-error feature.
+#[cfg(feature = "mock")]
+pub struct MockArea<const N: usize = EXCHANGE_AREA_LEN> {
+    data: core::cell::RefCell<[u8; N]>,
+    writes: Cell<usize>,
+}
+
+#[cfg(feature = "mock")]
+impl<const N: usize> MockArea<N> {
+    /// create a zeroed mock area
+    pub const fn new() -> Self {
+        Self {
+            data: core::cell::RefCell::new([0u8; N]),
+            writes: Cell::new(0),
+        }
+    }
+
+    /// current mock area content
+    pub fn contents(&self) -> [u8; N] {
+        *self.data.borrow()
+    }
+
+    /// number of successful copies to the mock area
+    pub fn write_count(&self) -> usize {
+        self.writes.get()
+    }
+
+    /// set the mock area first bytes, as the kernel would do. The bytes that
+    /// do not fit in the mock area are ignored.
+    pub fn load(&self, bytes: &[u8]) {
+        let len = bytes.len().min(N);
+        self.data.borrow_mut()[..len].copy_from_slice(&bytes[..len]);
+    }
+}
+
+#[cfg(feature = "mock")]
+impl<const N: usize> Default for MockArea<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "mock")]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+impl<const N: usize> ExchangeArea<u8> for MockArea<N> {
+
+    fn copy_vec_to(&self, from: *const u8, length: usize) -> Status {
+        self.copy_vec_to_at(0, from, length)
+    }
+
+    fn copy_vec_from(&self, to: *mut u8, length: usize) -> Status {
+        self.copy_vec_from_at(0, to, length)
+    }
+
+    fn copy_vec_to_counted(&self, from: *const u8, length: usize) -> (Status, usize) {
+        match self.copy_vec_to(from, length) {
+            Status::Ok => (Status::Ok, length),
+            status => (status, 0),
+        }
+    }
+
+    fn copy_vec_from_counted(&self, to: *mut u8, length: usize) -> (Status, usize) {
+        match self.copy_vec_from(to, length) {
+            Status::Ok => (Status::Ok, length),
+            status => (status, 0),
+        }
+    }
+
+    fn copy_vec_to_at(&self, offset: usize, from: *const u8, length: usize) -> Status {
+        let status = 'copy: {
+            if length == 0 {
+                break 'copy Status::Ok;
+            }
+            if from.is_null() {
+                break 'copy Status::NullPointer;
+            }
+            match offset.checked_add(length) {
+                Some(end) if end <= N => (),
+                Some(_) => break 'copy Status::TooLarge,
+                None => break 'copy Status::Overflow,
+            }
+            let src = unsafe { core::slice::from_raw_parts(from, length) };
+            self.data.borrow_mut()[offset..offset + length].copy_from_slice(src);
+            self.writes.set(self.writes.get() + 1);
+            Status::Ok
+        };
+        status.or_panic("copy to the area", offset, length)
+    }
+
+    fn copy_vec_from_at(&self, offset: usize, to: *mut u8, length: usize) -> Status {
+        let status = 'copy: {
+            if length == 0 {
+                break 'copy Status::Ok;
+            }
+            if to.is_null() {
+                break 'copy Status::NullPointer;
+            }
+            match offset.checked_add(length) {
+                Some(end) if end <= N => (),
+                Some(_) => break 'copy Status::TooLarge,
+                None => break 'copy Status::Overflow,
+            }
+            let dst = unsafe { core::slice::from_raw_parts_mut(to, length) };
+            dst.copy_from_slice(&self.data.borrow()[offset..offset + length]);
+            Status::Ok
+        };
+        status.or_panic("copy from the area", offset, length)
+    }
+
+    fn area_length(&self) -> usize {
+        N
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Mutex, MutexGuard};
+
+    /// the exchange area is a single global, serialize the tests that
+    /// manipulate it
+    static AREA_LOCK: Mutex<()> = Mutex::new(());
+
+    fn lock_area() -> MutexGuard<'static, ()> {
+        AREA_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// number of Area::sync() calls made by dropped ExchangeGuard
+    pub(super) static SYNCS: std::sync::atomic::AtomicUsize =
+        std::sync::atomic::AtomicUsize::new(0);
+
+    /// check that the copy fails with the given status
+    #[cfg(not(feature = "panic-on-error"))]
+    macro_rules! assert_fails {
+        ($copy:expr, $status:pat $(,)?) => {
+            assert!(matches!($copy, $status))
+        };
+    }
+
+    /// with the panic-on-error feature, check that the copy panics instead
+    /// of returning the failure status
+    #[cfg(feature = "panic-on-error")]
+    macro_rules! assert_fails {
+        ($copy:expr, $status:pat $(,)?) => {{
+            let copy = || {
+                let _ = $copy;
+            };
+            let Err(payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(copy)) else {
+                panic!("{} did not panic", stringify!($copy));
+            };
+            let message = payload.downcast_ref::<String>().map_or("", |m| m.as_str());
+            assert!(message.contains(" failed: "), "unexpected panic: {message}");
+        }};
+    }
+
+    #[test]
+    fn create_area() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert_eq!(<Area as ExchangeArea<u8>>::area_length(&area), 128);
+    }
+
+    #[test]
+    fn back_to_back_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b'z'; 100];
+        let mut res = [b'a'; 100];
+        assert!(matches!(area.copy_vec_to(string.as_ptr(), string.len()), Status::Ok));
+        assert!(matches!(area.copy_vec_from(res.as_mut_ptr(), string.len()), Status::Ok));
+        assert_eq!(res, string);
+    }
+
+    #[test]
+    fn back_to_back_counted_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
         let string = [b'z'; 100];
         let mut res = [b'a'; 100];
-        area.copy_vec_to(string.as_ptr(), string.len());
-        area.copy_vec_from(res.as_mut_ptr(), string.len());
+        let (_, written) = area.copy_vec_to_counted(string.as_ptr(), string.len());
+        let (_, read) = area.copy_vec_from_counted(res.as_mut_ptr(), string.len());
+        assert_eq!(written, string.len());
+        assert_eq!(read, string.len());
+        assert_eq!(res, string);
+    }
+
+    #[test]
+    fn back_to_back_shm_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfo = ShmInfo {
+            handle: 2,
+            label: 42,
+            base: 0x123456,
+            len: 64,
+            perms: 0x1,
+        };
+        let mut shminfo_copy = ShmInfo::default();
+        assert!(matches!(area.copy_to(&shminfo), Status::Ok));
+        assert!(matches!(area.copy_from(&mut shminfo_copy), Status::Ok));
+        assert_eq!(shminfo, shminfo_copy);
+    }
+
+    #[test]
+    fn too_large_copy_rejected() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let pattern = [b'p'; EXCHANGE_AREA_LEN];
+        let string = [b'z'; EXCHANGE_AREA_LEN + 1];
+        let mut res = [0u8; EXCHANGE_AREA_LEN + 1];
+        assert!(matches!(area.copy_vec_to(pattern.as_ptr(), pattern.len()), Status::Ok));
+        assert_fails!(
+            area.copy_vec_to_counted(string.as_ptr(), string.len()),
+            (Status::TooLarge, 0)
+        );
+        assert_fails!(
+            area.copy_vec_from_counted(res.as_mut_ptr(), res.len()),
+            (Status::TooLarge, 0)
+        );
+        assert!(matches!(area.copy_vec_from(res.as_mut_ptr(), pattern.len()), Status::Ok));
+        assert_eq!(res[..EXCHANGE_AREA_LEN], pattern);
+    }
+
+    #[test]
+    fn vec_copy_failure_reasons() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut res = [0u8; 16];
+        let inner = EXCHANGE_AREA.as_ptr();
+        assert_fails!(
+            area.copy_vec_to(core::ptr::null::<u8>(), 16),
+            Status::NullPointer
+        );
+        assert_fails!(
+            area.copy_vec_from(core::ptr::null_mut::<u8>(), 16),
+            Status::NullPointer
+        );
+        assert_fails!(area.copy_vec_to(inner.wrapping_add(4), 16), Status::Overlap);
+        assert_fails!(area.copy_vec_from(inner, 16), Status::Overlap);
+        assert_fails!(
+            area.copy_vec_from(res.as_mut_ptr(), EXCHANGE_AREA_LEN + 1),
+            Status::TooLarge
+        );
+    }
+
+    #[test]
+    fn back_to_back_slice_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b'y'; 64];
+        let mut res = [0u8; 64];
+        assert!(matches!(area.write_slice(&string), Status::Ok));
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res, string);
+    }
+
+    #[test]
+    fn too_large_slice_rejected() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b'z'; EXCHANGE_AREA_LEN + 1];
+        let mut res = [0u8; EXCHANGE_AREA_LEN + 1];
+        assert_fails!(area.write_slice(&string), Status::TooLarge);
+        assert_fails!(area.read_slice(&mut res), Status::TooLarge);
+    }
+
+    #[test]
+    fn back_to_back_u32_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let word: u32 = 0xDEADBEEF;
+        let mut word_copy: u32 = 0;
+        let mut raw = [0u8; 4];
+        assert!(matches!(area.copy_to(&word), Status::Ok));
+        assert!(matches!(area.copy_from(&mut word_copy), Status::Ok));
+        assert_eq!(word, word_copy);
+        assert!(matches!(area.copy_vec_from(raw.as_mut_ptr(), raw.len()), Status::Ok));
+        assert_eq!(raw, [0xEF, 0xBE, 0xAD, 0xDE]);
+    }
+
+    #[test]
+    fn sized_area() {
+        let _guard = lock_area();
+        let area = unsafe { Area::<64>::new_sized() };
+        let string = [b'x'; 65];
+        assert_eq!(<Area<64> as ExchangeArea<u8>>::area_length(&area), 64);
+        assert_fails!(area.write_slice(&string), Status::TooLarge);
+        assert!(matches!(area.write_slice(&string[..64]), Status::Ok));
+        assert_eq!(<Area128 as ExchangeArea<u8>>::area_length(&unsafe { Area128::new_sized() }), 128);
+    }
+
+    #[test]
+    fn write_then_sync() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b'w'; 16];
+        let mut res = [0u8; 16];
+        assert!(matches!(area.write_slice(&string), Status::Ok));
+        area.sync();
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res, string);
+    }
+
+    #[test]
+    fn written_len_tracking() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b'v'; 10];
+        let word: u32 = 42;
+        assert_eq!(area.written_len(), 0);
+        assert!(matches!(area.write_slice(&string), Status::Ok));
+        assert_eq!(area.written_len(), 10);
+        assert!(matches!(area.copy_to(&word), Status::Ok));
+        assert_eq!(area.written_len(), 4);
+        assert_fails!(area.write_slice(&[0u8; 200]), Status::TooLarge);
+        assert_eq!(area.written_len(), 4);
+    }
+
+    #[test]
+    fn clear_area() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let pattern = [0x5au8; EXCHANGE_AREA_LEN];
+        let mut res = [0xffu8; EXCHANGE_AREA_LEN];
+        assert!(matches!(area.write_slice(&pattern), Status::Ok));
+        area.clear();
+        assert_eq!(area.written_len(), 0);
+        unsafe { Area128::new().area_read_raw(0, res.as_mut_ptr(), res.len()) };
+        assert_eq!(res, [0u8; EXCHANGE_AREA_LEN]);
+    }
+
+    #[cfg(feature = "zeroize-on-drop")]
+    #[test]
+    fn zeroize_on_drop() {
+        let _guard = lock_area();
+        let pattern = [0xa5u8; EXCHANGE_AREA_LEN];
+        let storage = || {
+            let mut res = [0xffu8; EXCHANGE_AREA_LEN * AREA_SLOTS];
+            for (i, byte) in res.iter_mut().enumerate() {
+                *byte = unsafe { EXCHANGE_AREA.as_ptr().add(i).read_volatile() };
+            }
+            res
+        };
+        let area = Area::take().unwrap();
+        assert!(matches!(area.write_slice(&pattern), Status::Ok));
+        #[cfg(feature = "double-buffer")]
+        {
+            area.swap();
+            assert!(matches!(area.write_slice(&pattern), Status::Ok));
+            area.swap();
+        }
+        // the area is only wiped once all the owning handles are dropped
+        let clone = area.clone();
+        drop(area);
+        drop(unsafe { Area::new() });
+        assert!(storage().iter().all(|&byte| byte == 0xa5));
+        drop(clone);
+        assert!(storage().iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn area_capacity() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert_eq!(<Area as ExchangeArea<u8>>::area_capacity(&area), 128);
+        assert_eq!(<Area as ExchangeArea<u32>>::area_capacity(&area), 32);
+        assert_eq!(
+            <Area as ExchangeArea<ShmInfo>>::area_capacity(&area),
+            128 / core::mem::size_of::<ShmInfo>()
+        );
+        let small = unsafe { Area::<16>::new_sized() };
+        assert_eq!(<Area<16> as ExchangeArea<u32>>::area_capacity(&small), 4);
+    }
+
+    #[test]
+    fn back_to_back_shm_vec_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfos = [
+            ShmInfo { handle: 1, label: 10, base: 0x1000, len: 16, perms: 0x1 },
+            ShmInfo { handle: 2, label: 20, base: 0x2000, len: 32, perms: 0x3 },
+            ShmInfo { handle: 3, label: 30, base: 0x3000, len: 64, perms: 0x7 },
+        ];
+        let mut shminfos_copy = [ShmInfo::default(); 3];
+        assert!(matches!(
+            area.copy_vec_to_counted(shminfos.as_ptr(), shminfos.len()),
+            (Status::Ok, 3)
+        ));
+        assert!(matches!(
+            area.copy_vec_from_counted(shminfos_copy.as_mut_ptr(), shminfos_copy.len()),
+            (Status::Ok, 3)
+        ));
+        for (shminfo, shminfo_copy) in shminfos.iter().zip(shminfos_copy.iter()) {
+            assert_eq!(shminfo, shminfo_copy);
+        }
+        let capacity = <Area as ExchangeArea<ShmInfo>>::area_capacity(&area);
+        assert_fails!(
+            area.copy_vec_to(shminfos.as_ptr(), capacity + 1),
+            Status::TooLarge
+        );
+    }
+
+    #[test]
+    fn overflowing_vec_length_rejected() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfo = ShmInfo { handle: 1, label: 2, base: 3, len: 4, perms: 5 };
+        let mut shminfo_copy = shminfo;
+        assert_fails!(area.copy_vec_to(&shminfo, usize::MAX), Status::Overflow);
+        assert_fails!(
+            area.copy_vec_from(&mut shminfo_copy, usize::MAX),
+            Status::Overflow
+        );
+        assert_eq!(area.written_len(), 0);
+    }
+
+    #[test]
+    fn adjacent_buffers_accepted() {
+        let area = EXCHANGE_AREA.as_ptr() as *const u8;
+        let area_end = area.wrapping_add(EXCHANGE_AREA_LEN * AREA_SLOTS);
+        // buffer ending exactly at the area start
+        assert!(unsafe { Area128::new() }.check_overlapping(area.wrapping_sub(16), 16).is_ok());
+        // buffer starting exactly at the area end
+        assert!(unsafe { Area128::new() }.check_overlapping(area_end, 16).is_ok());
+        // one byte more on each side overlaps
+        assert!(unsafe { Area128::new() }.check_overlapping(area.wrapping_sub(16), 17).is_err());
+        assert!(unsafe { Area128::new() }.check_overlapping(area_end.wrapping_sub(1), 16).is_err());
+    }
+
+    #[test]
+    fn wrapping_buffer_rejected() {
+        let pointer = (usize::MAX - 8) as *const u8;
+        assert!(unsafe { Area128::new() }.check_overlapping(pointer, 16).is_err());
+        assert!(unsafe { Area128::new() }.check_overlapping(pointer, usize::MAX).is_err());
+        assert!(unsafe { Area128::new() }.check_overlapping(pointer, 8).is_ok());
+    }
+
+    #[test]
+    fn overlapping_shm_copy_rejected() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let inner = EXCHANGE_AREA.as_ptr() as *mut ShmInfo;
+        assert_fails!(area.copy_from(inner), Status::Overlap);
+        assert_fails!(area.copy_to(inner as *const ShmInfo), Status::Overlap);
+        assert_eq!(area.written_len(), 0);
+    }
+
+    #[test]
+    fn null_pointers_rejected() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert_fails!(
+            area.copy_vec_to_counted(core::ptr::null::<u8>(), 16),
+            (Status::NullPointer, 0)
+        );
+        assert_fails!(
+            area.copy_vec_from_counted(core::ptr::null_mut::<u8>(), 16),
+            (Status::NullPointer, 0)
+        );
+        assert_fails!(area.copy_to(core::ptr::null::<u32>()), Status::NullPointer);
+        assert_fails!(area.copy_from(core::ptr::null_mut::<u32>()), Status::NullPointer);
+        assert_fails!(
+            area.copy_to(core::ptr::null::<ShmInfo>()),
+            Status::NullPointer
+        );
+        assert_fails!(
+            area.copy_from(core::ptr::null_mut::<ShmInfo>()),
+            Status::NullPointer
+        );
+        assert_fails!(
+            area.copy_vec_to(core::ptr::null::<ShmInfo>(), 2),
+            Status::NullPointer
+        );
+        assert_fails!(
+            area.copy_vec_from(core::ptr::null_mut::<ShmInfo>(), 2),
+            Status::NullPointer
+        );
+        assert_eq!(area.written_len(), 0);
+    }
+
+    #[test]
+    fn misaligned_shm_copy_rejected() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut storage = [ShmInfo::default(); 2];
+        let misaligned = (storage.as_mut_ptr() as *mut u8).wrapping_add(1) as *mut ShmInfo;
+        assert_fails!(area.copy_from(misaligned), Status::Misaligned);
+        assert_fails!(area.copy_to(misaligned as *const ShmInfo), Status::Misaligned);
+        assert_fails!(area.copy_vec_from(misaligned, 1), Status::Misaligned);
+        assert_fails!(
+            area.copy_vec_to(misaligned as *const ShmInfo, 1),
+            Status::Misaligned
+        );
+    }
+
+    #[test]
+    fn formatted_write_chunks() {
+        use core::fmt::Write;
+        let _guard = lock_area();
+        let area = unsafe { Area::<16>::new_sized() };
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        let mut writer = AreaWriter::new(&area, |len| {
+            let mut chunk = vec![0u8; len];
+            assert!(matches!(area.read_slice(&mut chunk), Status::Ok));
+            chunks.push(chunk);
+        });
+        assert!(write!(writer, "hello-{:08x}-exchange area", 0xdeadbeefu32).is_ok());
+        assert!(matches!(writer.flush(), Status::Ok));
+        assert!(matches!(writer.flush(), Status::Ok));
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], b"hello-deadbeef-e");
+        assert_eq!(chunks[1], b"xchange area");
+    }
+
+    #[test]
+    fn formatted_write_empty_area() {
+        use core::fmt::Write;
+        let _guard = lock_area();
+        let area = unsafe { Area::<0>::new_sized() };
+        let mut flushes = 0;
+        let mut writer = AreaWriter::new(&area, |_len| flushes += 1);
+        assert!(writer.write_str("").is_ok());
+        assert!(writer.write_str("x").is_err());
+        assert!(matches!(writer.flush(), Status::Ok));
+        assert_eq!(flushes, 0);
+    }
+
+    #[test]
+    fn write_str_boundary() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut res = [0u8; EXCHANGE_AREA_LEN];
+        let ascii = "a".repeat(EXCHANGE_AREA_LEN);
+        assert!(matches!(area.write_str(&ascii), Status::Ok));
+        assert_eq!(area.written_len(), EXCHANGE_AREA_LEN);
+        assert_fails!(area.write_str(&"a".repeat(EXCHANGE_AREA_LEN + 1)), Status::TooLarge);
+        // 42 chars, 126 bytes
+        let multibyte = "\u{20ac}".repeat(42);
+        assert!(matches!(area.write_str(&multibyte), Status::Ok));
+        assert_eq!(area.written_len(), 126);
+        assert!(matches!(area.read_slice(&mut res[..126]), Status::Ok));
+        assert_eq!(&res[..126], multibyte.as_bytes());
+        // 43 chars, 129 bytes
+        let multibyte = "\u{20ac}".repeat(43);
+        assert_fails!(area.write_str(&multibyte), Status::TooLarge);
+        assert_eq!(area.written_len(), 126);
+    }
+
+    #[test]
+    fn read_str_validation() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut res = [0u8; EXCHANGE_AREA_LEN + 1];
+        assert!(matches!(area.write_str("caf\u{e9} cr\u{e8}me"), Status::Ok));
+        assert_eq!(area.read_str(&mut res, 12).ok(), Some("caf\u{e9} cr\u{e8}me"));
+        // the two bytes encoding of 'é' is cut in the middle
+        assert_fails!(area.read_str(&mut res, 4), Err(Status::InvalidUtf8));
+        assert!(matches!(area.write_slice(&[b'o', b'k', 0xff, 0xfe]), Status::Ok));
+        assert_eq!(area.read_str(&mut res, 2).ok(), Some("ok"));
+        assert_fails!(area.read_str(&mut res, 4), Err(Status::InvalidUtf8));
+        assert_fails!(
+            area.read_str(&mut res, EXCHANGE_AREA_LEN + 1),
+            Err(Status::TooLarge)
+        );
+        assert_fails!(area.read_str(&mut res[..2], 4), Err(Status::TooLarge));
+    }
+
+    #[test]
+    fn copy_at_offsets() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let header = [b'h'; 8];
+        let body = [b'b'; 32];
+        let mut header_copy = [0u8; 8];
+        let mut body_copy = [0u8; 32];
+        assert!(matches!(area.copy_vec_to_at(0, header.as_ptr(), header.len()), Status::Ok));
+        assert!(matches!(area.copy_vec_to_at(64, body.as_ptr(), body.len()), Status::Ok));
+        assert_eq!(area.written_len(), 96);
+        assert!(matches!(
+            area.copy_vec_from_at(0, header_copy.as_mut_ptr(), header_copy.len()),
+            Status::Ok
+        ));
+        assert!(matches!(
+            area.copy_vec_from_at(64, body_copy.as_mut_ptr(), body_copy.len()),
+            Status::Ok
+        ));
+        assert_eq!(header_copy, header);
+        assert_eq!(body_copy, body);
+        assert_fails!(
+            area.copy_vec_to_at(EXCHANGE_AREA_LEN - 31, body.as_ptr(), body.len()),
+            Status::TooLarge
+        );
+        assert_fails!(
+            area.copy_vec_from_at(usize::MAX, body_copy.as_mut_ptr(), body_copy.len()),
+            Status::Overflow
+        );
+        assert_eq!(area.written_len(), 96);
+    }
+
+    #[test]
+    fn cursor_sequential_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let payload = [b'c'; 16];
+        let mut payload_copy = [0u8; 16];
+        let mut cursor = AreaCursor::new(&area);
+        assert!(matches!(cursor.put_u32_le(0xCAFEF00D), Status::Ok));
+        assert!(matches!(cursor.put_slice(&payload), Status::Ok));
+        assert!(matches!(cursor.put_u8(0x42), Status::Ok));
+        assert_eq!(cursor.remaining(), EXCHANGE_AREA_LEN - 21);
+        assert_fails!(cursor.put_slice(&[0u8; EXCHANGE_AREA_LEN]), Status::TooLarge);
+        assert_eq!(cursor.remaining(), EXCHANGE_AREA_LEN - 21);
+
+        let mut cursor = AreaCursor::new(&area);
+        assert_eq!(cursor.get_u32_le().ok(), Some(0xCAFEF00D));
+        assert!(matches!(cursor.get_slice(&mut payload_copy), Status::Ok));
+        assert_eq!(payload_copy, payload);
+        assert_eq!(cursor.get_u8().ok(), Some(0x42));
+    }
+
+    #[test]
+    fn cursor_end_of_area() {
+        let _guard = lock_area();
+        let area = unsafe { Area::<6>::new_sized() };
+        let mut cursor = AreaCursor::new(&area);
+        assert!(matches!(cursor.put_u32_le(1), Status::Ok));
+        assert_fails!(cursor.put_u32_le(2), Status::TooLarge);
+        assert!(matches!(cursor.put_u8(3), Status::Ok));
+        assert!(matches!(cursor.put_u8(4), Status::Ok));
+        assert_fails!(cursor.put_u8(5), Status::TooLarge);
+        assert_eq!(cursor.remaining(), 0);
+        let mut cursor = AreaCursor::new(&area);
+        assert_eq!(cursor.get_u32_le().ok(), Some(1));
+        assert_fails!(cursor.get_u32_le(), Err(Status::TooLarge));
+        assert_eq!(cursor.remaining(), 2);
+    }
+
+    #[test]
+    fn const_and_default_area() {
+        let _guard = lock_area();
+        let area = const { unsafe { Area::new() } };
+        assert_eq!(area.written_len(), 0);
+        let area = Area::default();
+        assert_eq!(<Area as ExchangeArea<u8>>::area_length(&area), EXCHANGE_AREA_LEN);
+        let small = const { unsafe { Area::<32>::new_sized() } };
+        assert_eq!(<Area<32> as ExchangeArea<u8>>::area_length(&small), 32);
+        drop(area);
+        let small = Area::<32>::default();
+        assert_eq!(<Area<32> as ExchangeArea<u8>>::area_length(&small), 32);
+    }
+
+    #[test]
+    fn debug_and_clone_area() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_slice(&[b'd'; 12]), Status::Ok));
+        let clone = area.clone();
+        assert_eq!(clone.written_len(), 12);
+        assert_eq!(format!("{:?}", clone), "Area { length: 128, valid_len: 12 }");
+    }
+
+    #[test]
+    fn take_once() {
+        let _guard = lock_area();
+        let area = Area::take();
+        assert!(area.is_some());
+        assert!(Area::take().is_none());
+        assert!(Area::<64>::take_sized().is_none());
+        // a clone shares the area ownership
+        let clone = area.clone();
+        drop(area);
+        assert!(Area::take().is_none());
+        drop(clone);
+        let area = Area::take();
+        assert!(area.is_some());
+        drop(area);
+    }
+
+    #[test]
+    fn try_copies_chaining() {
+        fn exchange(area: &Area, msg: &[u8], res: &mut [u8]) -> Result<usize, ExchangeError> {
+            let written = area.try_copy_vec_to(msg.as_ptr(), msg.len())?;
+            area.try_copy_vec_from(res.as_mut_ptr(), written)
+        }
+
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let msg = [b't'; 32];
+        let mut res = [0u8; 32];
+        assert_eq!(exchange(&area, &msg, &mut res), Ok(32));
+        assert_eq!(res, msg);
+        assert_fails!(
+            exchange(&area, &[0u8; EXCHANGE_AREA_LEN + 1], &mut res),
+            Err(ExchangeError::TooLarge)
+        );
+
+        let word: u32 = 0x12345678;
+        let mut word_copy: u32 = 0;
+        assert_eq!(area.try_copy_to(&word), Ok(()));
+        assert_eq!(area.try_copy_from(&mut word_copy), Ok(()));
+        assert_eq!(word_copy, word);
+        assert_fails!(
+            area.try_copy_from(core::ptr::null_mut::<u32>()),
+            Err(ExchangeError::NullPointer)
+        );
+        assert_fails!(
+            <Area as ExchangeArea<u8>>::try_copy_to(&area, &0u8),
+            Err(ExchangeError::Invalid)
+        );
+    }
+
+    #[test]
+    fn exchange_error_display() {
+        let messages = [
+            (ExchangeError::Invalid, "invalid exchange"),
+            (ExchangeError::TooLarge, "data do not fit in exchange area"),
+            (ExchangeError::Overlap, "buffer overlaps exchange area"),
+            (ExchangeError::NullPointer, "null buffer pointer"),
+            (ExchangeError::Misaligned, "misaligned buffer pointer"),
+            (ExchangeError::Overflow, "copy length overflows"),
+            (ExchangeError::InvalidUtf8, "invalid UTF-8 in exchange area"),
+            (ExchangeError::ShortRead, "read beyond exchange area written data"),
+        ];
+        for (error, message) in messages {
+            assert_eq!(error.to_string(), message);
+            let error: &dyn core::error::Error = &error;
+            assert!(error.source().is_none());
+        }
+    }
+
+    #[test]
+    fn shm_accessors() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfo = ShmInfo { handle: 7, label: 0x42, base: 0x2000_0000, len: 256, perms: 0x3 };
+        let mut shminfo_copy = ShmInfo::default();
+        assert!(matches!(area.copy_to(&shminfo), Status::Ok));
+        assert!(matches!(area.copy_from(&mut shminfo_copy), Status::Ok));
+        assert_eq!(shminfo_copy.handle(), 7);
+        assert_eq!(shminfo_copy.label(), 0x42);
+        assert_eq!(shminfo_copy.base(), 0x2000_0000);
+        assert_eq!(shminfo_copy.len(), 256);
+        assert_eq!(shminfo_copy.perms(), ShmPerms::READ | ShmPerms::WRITE);
+    }
+
+    #[test]
+    fn shm_new() {
+        let shminfo = ShmInfo::new(3, 0xf00, 0x2000_1000, 512, 0x1);
+        assert_eq!(
+            shminfo,
+            ShmInfo { handle: 3, label: 0xf00, base: 0x2000_1000, len: 512, perms: 0x1 }
+        );
+    }
+
+    #[test]
+    fn shm_perms_bits() {
+        assert_eq!(ShmPerms::from_bits(0x1), Some(ShmPerms::READ));
+        assert_eq!(ShmPerms::from_bits(0x6), Some(ShmPerms::WRITE | ShmPerms::MAP));
+        assert_eq!(ShmPerms::from_bits(0x10), None);
+        assert_eq!(ShmPerms::from_bits_retain(0x11).bits(), 0x11);
+        assert_eq!(ShmPerms::all().bits(), 0xf);
+        assert!(ShmPerms::empty().is_empty());
+        let perms = ShmInfo { handle: 1, label: 2, base: 3, len: 4, perms: 0x5 }.perms();
+        assert!(perms.contains(ShmPerms::READ | ShmPerms::MAP));
+        assert!(!perms.contains(ShmPerms::WRITE));
+        assert_eq!(perms & ShmPerms::MAP, ShmPerms::MAP);
+    }
+
+    #[test]
+    fn area_matches() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut pattern = [0u8; 48];
+        for (i, byte) in pattern.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert!(matches!(area.write_slice(&pattern), Status::Ok));
+        assert!(area.matches(&pattern));
+        assert!(area.matches(&pattern[..10]));
+        assert!(area.matches(&[]));
+        assert!(!area.matches(&[0u8; EXCHANGE_AREA_LEN + 1]));
+        let mut byte = [0xffu8];
+        assert!(matches!(area.copy_vec_to_at(47, byte.as_mut_ptr(), 1), Status::Ok));
+        assert!(!area.matches(&pattern));
+        assert!(area.matches(&pattern[..47]));
+    }
+
+    #[test]
+    fn area_checksum() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_str("123456789"), Status::Ok));
+        assert_eq!(area.checksum(9).ok(), Some(0xCBF4_3926));
+        assert_eq!(area.checksum(0).ok(), Some(0));
+        assert!(matches!(area.checksum(EXCHANGE_AREA_LEN + 1), Err(Status::TooLarge)));
+        assert!(matches!(area.write_str("123456788"), Status::Ok));
+        assert_ne!(area.checksum(9).ok(), Some(0xCBF4_3926));
+    }
+
+    #[test]
+    fn back_to_back_array_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let array = [0x3cu8; 64];
+        let mut array_copy = [0u8; 64];
+        assert!(matches!(area.copy_to(&array), Status::Ok));
+        assert_eq!(area.written_len(), 64);
+        assert!(matches!(area.copy_from(&mut array_copy), Status::Ok));
+        assert_eq!(array_copy, array);
+        let small = unsafe { Area::<32>::new_sized() };
+        assert_fails!(small.copy_to(&array), Status::TooLarge);
+    }
+
+    #[test]
+    fn back_to_back_integer_copies() {
+        fn round_trip<T>(area: &Area, value: T, le_bytes: &[u8])
+        where
+            T: Default + PartialEq + core::fmt::Debug,
+            Area: ExchangeArea<T>,
+        {
+            let mut copy = T::default();
+            let mut raw = [0u8; 8];
+            assert!(matches!(area.copy_to(&value), Status::Ok));
+            assert_eq!(area.written_len(), le_bytes.len());
+            assert!(matches!(area.copy_from(&mut copy), Status::Ok));
+            assert_eq!(copy, value);
+            assert!(matches!(area.read_slice(&mut raw[..le_bytes.len()]), Status::Ok));
+            assert_eq!(&raw[..le_bytes.len()], le_bytes);
+        }
+
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        round_trip(&area, 0x1234u16, &[0x34, 0x12]);
+        round_trip(&area, u16::MAX, &[0xff; 2]);
+        round_trip(&area, 0x12345678u32, &[0x78, 0x56, 0x34, 0x12]);
+        round_trip(&area, u32::MAX, &[0xff; 4]);
+        round_trip(&area, 0x0102030405060708u64, &[8, 7, 6, 5, 4, 3, 2, 1]);
+        round_trip(&area, u64::MAX, &[0xff; 8]);
+        round_trip(&area, -2i16, &[0xfe, 0xff]);
+        round_trip(&area, i16::MAX, &[0xff, 0x7f]);
+        round_trip(&area, i16::MIN, &[0x00, 0x80]);
+        round_trip(&area, i32::MAX, &[0xff, 0xff, 0xff, 0x7f]);
+        round_trip(&area, i32::MIN, &[0x00, 0x00, 0x00, 0x80]);
+        round_trip(&area, i64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+        round_trip(&area, i64::MIN, &[0, 0, 0, 0, 0, 0, 0, 0x80]);
+        let small = unsafe { Area::<4>::new_sized() };
+        assert_fails!(small.copy_to(&u64::MAX), Status::TooLarge);
+    }
+
+    #[test]
+    fn read_by_value() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfo = ShmInfo::new(4, 0x1234, 0x2000_4000, 1024, 0x3);
+        assert!(matches!(area.copy_to(&shminfo), Status::Ok));
+        assert_eq!(area.read::<ShmInfo>().ok(), Some(shminfo));
+        assert!(matches!(area.copy_to(&0xA5A5_5A5Au32), Status::Ok));
+        assert_eq!(area.read::<u32>().ok(), Some(0xA5A5_5A5A));
+        assert_fails!(area.read::<u8>(), Err(Status::Invalid));
+        let small = unsafe { Area::<2>::new_sized() };
+        assert_fails!(small.read::<u32>(), Err(Status::TooLarge));
+    }
+
+    #[test]
+    fn back_to_back_shm_uninit_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfo = ShmInfo::new(5, 0x99, 0x2000_8000, 128, 0x1);
+        let mut shminfo_copy = core::mem::MaybeUninit::<ShmInfo>::uninit();
+        assert!(matches!(area.copy_to(&shminfo), Status::Ok));
+        assert!(matches!(area.copy_from_uninit(&mut shminfo_copy), Status::Ok));
+        assert_eq!(unsafe { shminfo_copy.assume_init() }, shminfo);
+    }
+
+    #[test]
+    fn shm_info_iteration() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfos = [
+            ShmInfo::new(1, 0x10, 0x2000_0000, 64, 0x1),
+            ShmInfo::new(2, 0x20, 0x2000_1000, 128, 0x3),
+        ];
+        assert!(matches!(area.copy_vec_to(shminfos.as_ptr(), shminfos.len()), Status::Ok));
+        let collected: Vec<ShmInfo> = area.shm_info_iter(2).collect();
+        assert_eq!(collected, shminfos);
+        assert_eq!(area.shm_info_iter(1).next(), Some(shminfos[0]));
+        let capacity = <Area as ExchangeArea<ShmInfo>>::area_capacity(&area);
+        let written = if cfg!(feature = "strict-read") { 2 } else { capacity };
+        assert_eq!(area.shm_info_iter(usize::MAX).count(), written);
+    }
+
+    #[test]
+    fn extern_c_copies() {
+        let _guard = lock_area();
+        let string = [b'c'; 24];
+        let mut res = [0u8; 24];
+        unsafe {
+            assert_eq!(svcexch_copy_to(string.as_ptr(), string.len()), 0);
+            assert_eq!(svcexch_copy_from(res.as_mut_ptr(), res.len()), 0);
+            assert_eq!(svcexch_copy_to(core::ptr::null(), 4), -4);
+            assert_eq!(svcexch_copy_from(res.as_mut_ptr(), EXCHANGE_AREA_LEN + 1), -2);
+        }
         assert_eq!(res, string);
     }
 
     #[test]
-    fn back_to_back_shm_copy() {
-        let area = Area::new();
-        let shminfo = ShmInfo {
-            handle: 2,
-            label: 42,
-            base: 0x123456,
-            len: 64,
-            perms: 0x1,
-        };
-        let mut shminfo_copy = ShmInfo {
-            handle: 0,
-            label: 0,
-            base: 0,
-            len: 0,
-            perms: 0,
+    fn area_bytes_view() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_str("svc exchange"), Status::Ok));
+        let bytes = unsafe { area.as_bytes(12) };
+        assert_eq!(bytes.ok(), Some(&b"svc exchange"[..]));
+        assert!(matches!(
+            unsafe { area.as_bytes(EXCHANGE_AREA_LEN + 1) },
+            Err(Status::TooLarge)
+        ));
+    }
+
+    #[test]
+    fn area_hexdump() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let payload = *b"svc exchange area\x00\xff";
+        assert!(matches!(area.copy_to(&payload), Status::Ok));
+        let mut dump = String::new();
+        assert!(area.hexdump(payload.len(), &mut dump).is_ok());
+        assert_eq!(
+            dump,
+            "0000  73 76 63 20 65 78 63 68 61 6e 67 65 20 61 72 65  |svc exchange are|\n\
+             0010  61 00 ff                                         |a..|\n"
+        );
+        let mut dump = String::new();
+        assert!(area.hexdump(usize::MAX, &mut dump).is_ok());
+        assert_eq!(dump.lines().count(), EXCHANGE_AREA_LEN / 16);
+    }
+
+    /// controlled area base, for the overlap checks unit tests
+    const TEST_AREA: usize = 0x2000_0000;
+
+    fn check_test_area(pointer: usize, length: usize) -> Result<(), ()> {
+        Area128::check_overlapping_in(TEST_AREA as *const u8, 128, pointer as *const u8, length)
+    }
+
+    #[test]
+    fn overlap_buffer_starts_inside() {
+        assert!(check_test_area(TEST_AREA, 1).is_err());
+        assert!(check_test_area(TEST_AREA + 64, 256).is_err());
+        assert!(check_test_area(TEST_AREA + 127, 1).is_err());
+    }
+
+    #[test]
+    fn overlap_buffer_ends_inside() {
+        assert!(check_test_area(TEST_AREA - 16, 17).is_err());
+        assert!(check_test_area(TEST_AREA - 256, 256 + 128).is_err());
+        assert!(check_test_area(0, TEST_AREA + 1).is_err());
+    }
+
+    #[test]
+    fn overlap_buffer_contains_area() {
+        assert!(check_test_area(TEST_AREA - 1, 130).is_err());
+        assert!(check_test_area(TEST_AREA - 0x1000, 0x2000).is_err());
+    }
+
+    #[test]
+    fn disjoint_buffers_accepted() {
+        assert!(check_test_area(TEST_AREA - 16, 16).is_ok());
+        assert!(check_test_area(TEST_AREA + 128, 16).is_ok());
+        assert!(check_test_area(0x1000, 0x1000).is_ok());
+        assert!(check_test_area(TEST_AREA + 0x1000, 0).is_ok());
+    }
+
+    #[cfg(feature = "strict-read")]
+    #[test]
+    fn short_read_rejected() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b's'; 10];
+        let mut res = [0u8; 50];
+        assert!(matches!(area.write_slice(&string), Status::Ok));
+        assert_fails!(
+            area.copy_vec_from_counted(res.as_mut_ptr(), 50),
+            (Status::ShortRead, 0)
+        );
+        assert!(matches!(area.copy_vec_from(res.as_mut_ptr(), 10), Status::Ok));
+        assert_eq!(res[..10], string);
+
+        assert_fails!(area.copy_vec_from_at(8, res.as_mut_ptr(), 4), Status::ShortRead);
+        assert!(matches!(area.peek_u8(9), Ok(b's')));
+        assert_fails!(area.peek_u32_le(8), Err(Status::ShortRead));
+        assert_eq!(area.get(9), Some(b's'));
+        assert_eq!(area.get(10), None);
+        let mut word = 0u32;
+        assert!(matches!(area.copy_from(&mut word), Status::Ok));
+        assert_fails!(area.read::<[u8; 16]>(), Err(Status::ShortRead));
+        let mut cursor = AreaCursor::new(&area);
+        assert!(matches!(cursor.get_slice(&mut res[..8]), Status::Ok));
+        assert_fails!(cursor.get_slice(&mut res[..8]), Status::ShortRead);
+        assert_eq!(area.snapshot()[..10], string);
+    }
+
+    #[test]
+    fn split_header_body_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let header: u64 = 0x0000_0020_0000_0001;
+        let body = [b'B'; 32];
+        let mut header_copy: u64 = 0;
+        let mut body_copy = [0u8; 32];
+        assert!(matches!(area.copy_to(&header), Status::Ok));
+        assert!(matches!(area.copy_vec_to_at(8, body.as_ptr(), body.len()), Status::Ok));
+        assert!(matches!(
+            area.copy_split_from(&mut header_copy, &mut body_copy),
+            Status::Ok
+        ));
+        assert_eq!(header_copy, header);
+        assert_eq!(body_copy, body);
+
+        let mut header_copy: u64 = 0;
+        let mut body_copy = [0u8; EXCHANGE_AREA_LEN];
+        assert_fails!(
+            area.copy_split_from(&mut header_copy, &mut body_copy[..EXCHANGE_AREA_LEN - 7]),
+            Status::TooLarge
+        );
+        assert_eq!(header_copy, 0);
+    }
+
+    #[test]
+    fn shm_little_endian_layout() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfo = ShmInfo::new(0x01020304, 0x05060708, 0x2000_0010, 0x40, 0x3);
+        let mut raw = [0xffu8; core::mem::size_of::<ShmInfo>()];
+        assert!(matches!(area.copy_to(&shminfo), Status::Ok));
+        assert!(matches!(area.read_slice(&mut raw), Status::Ok));
+        let field = |name_offset: usize, len: usize| &raw[name_offset..name_offset + len];
+        let word = core::mem::size_of::<usize>();
+        assert_eq!(field(core::mem::offset_of!(ShmInfo, handle), 4), [4, 3, 2, 1]);
+        assert_eq!(field(core::mem::offset_of!(ShmInfo, label), 4), [8, 7, 6, 5]);
+        assert_eq!(field(core::mem::offset_of!(ShmInfo, base), word), &0x2000_0010usize.to_le_bytes());
+        assert_eq!(field(core::mem::offset_of!(ShmInfo, len), word), &0x40usize.to_le_bytes());
+        assert_eq!(field(core::mem::offset_of!(ShmInfo, perms), 4), [3, 0, 0, 0]);
+        #[cfg(target_pointer_width = "64")]
+        {
+            assert_eq!(field(8, 8), [0x10, 0x00, 0x00, 0x20, 0, 0, 0, 0]);
+            // trailing padding is zeroed
+            assert_eq!(field(28, 4), [0u8; 4]);
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_trait_alias() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b'e'; 8];
+        assert!(matches!(ExhangeArea::copy_vec_to(&area, string.as_ptr(), 8), Status::Ok));
+        assert_eq!(<Area as ExhangeArea<u8>>::area_length(&area), EXCHANGE_AREA_LEN);
+    }
+
+    #[test]
+    fn unchecked_vec_copy() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let string = [b'u'; 40];
+        let mut res = [0u8; 40];
+        let large = [b'l'; EXCHANGE_AREA_LEN + 1];
+        unsafe {
+            assert!(matches!(
+                area.copy_vec_to_unchecked(large.as_ptr(), large.len()),
+                Status::Ok
+            ));
+            assert_eq!(area.written_len(), EXCHANGE_AREA_LEN);
+            assert!(matches!(area.copy_vec_to_unchecked(string.as_ptr(), 40), Status::Ok));
+            assert_fails!(
+                area.copy_vec_to_unchecked(core::ptr::null(), 4),
+                Status::NullPointer
+            );
+            assert!(matches!(area.copy_vec_to_unchecked(core::ptr::null(), 0), Status::Ok));
+        }
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res, string);
+        assert_eq!(area.written_len(), 40);
+    }
+
+    #[test]
+    fn word_and_byte_copy_paths() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut pattern = [0u8; 40];
+        for (i, byte) in pattern.iter_mut().enumerate() {
+            *byte = i as u8 + 1;
+        }
+        // unaligned user buffer, word multiple length
+        let (src, mut res) = (&pattern[1..33], [0u8; 33]);
+        assert!(matches!(area.write_slice(src), Status::Ok));
+        assert!(matches!(area.read_slice(&mut res[1..]), Status::Ok));
+        assert_eq!(&res[1..], src);
+        // word offset and length, then byte offset
+        for offset in [8, 3] {
+            let mut res = [0u8; 16];
+            assert!(matches!(area.copy_vec_to_at(offset, pattern.as_ptr(), 16), Status::Ok));
+            assert!(matches!(area.copy_vec_from_at(offset, res.as_mut_ptr(), 16), Status::Ok));
+            assert_eq!(res, pattern[..16]);
+        }
+        // odd length
+        let mut res = [0u8; 7];
+        assert!(matches!(area.write_slice(&pattern[..7]), Status::Ok));
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res, pattern[..7]);
+    }
+
+    #[cfg(feature = "double-buffer")]
+    #[test]
+    fn double_buffer_swap() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let first = [0x11u8; 32];
+        let second = [0x22u8; 32];
+        let mut res = [0u8; 32];
+        let active = area.active_slot();
+        assert!(matches!(area.write_slice(&first), Status::Ok));
+        area.swap();
+        assert_eq!(area.active_slot(), 1 - active);
+        // prepare the next payload while the first one is active
+        assert!(matches!(area.write_slice(&second), Status::Ok));
+        area.swap();
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res, first);
+        area.swap();
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res, second);
+        area.swap();
+        assert_eq!(area.active_slot(), active);
+    }
+
+    #[test]
+    fn remaining_after_write() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert_eq!(area.remaining(), EXCHANGE_AREA_LEN);
+        assert!(matches!(area.write_slice(&[b'r'; 100]), Status::Ok));
+        assert_eq!(area.remaining(), 28);
+        let tail = [b't'; 28];
+        assert!(matches!(area.copy_vec_to_at(100, tail.as_ptr(), tail.len()), Status::Ok));
+        assert_eq!(area.remaining(), 0);
+        area.clear();
+        assert_eq!(area.remaining(), EXCHANGE_AREA_LEN);
+    }
+
+    #[test]
+    fn append_slices() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut res = [0u8; 12];
+        assert!(matches!(area.write_slice(b"head"), Status::Ok));
+        assert!(matches!(area.append(b"-mid"), Status::Ok));
+        assert!(matches!(area.append(b"-end"), Status::Ok));
+        assert_eq!(area.written_len(), 12);
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(&res, b"head-mid-end");
+        assert_fails!(area.append(&[0u8; EXCHANGE_AREA_LEN - 11]), Status::TooLarge);
+        assert_eq!(area.written_len(), 12);
+    }
+
+    #[test]
+    fn reset_written_data() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.append(b"abandon"), Status::Ok));
+        area.reset();
+        assert_eq!(area.written_len(), 0);
+        // the area content is kept
+        assert!(area.matches(b"abandon"));
+        assert!(matches!(area.append(b"new"), Status::Ok));
+        assert_eq!(area.written_len(), 3);
+        assert!(area.matches(b"newndon"));
+    }
+
+    #[test]
+    fn shm_info_validation() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let valid = ShmInfo::new(1, 0x42, 0x2000_0000, 256, 0x3);
+        assert_eq!(valid.validate(), Ok(()));
+        assert!(matches!(area.copy_to(&valid), Status::Ok));
+        assert_eq!(area.read_shm_info_validated(), Ok(valid));
+
+        let malformed = [
+            (ShmInfo::new(0, 0x42, 0x2000_0000, 256, 0x3), ShmInfoError::NullHandle),
+            (ShmInfo::new(1, 0x42, 0x2000_0000, 0, 0x3), ShmInfoError::EmptyLength),
+            (ShmInfo::new(1, 0x42, usize::MAX - 16, 256, 0x3), ShmInfoError::AddressOverflow),
+            (ShmInfo::new(1, 0x42, 0x2000_0000, 256, 0x13), ShmInfoError::UndefinedPerms),
+        ];
+        for (shminfo, error) in malformed {
+            assert_eq!(shminfo.validate(), Err(error));
+            assert!(matches!(area.copy_to(&shminfo), Status::Ok));
+            assert_eq!(area.read_shm_info_validated(), Err(error));
+        }
+        let small = unsafe { Area::<8>::new_sized() };
+        assert_fails!(
+            small.read_shm_info_validated(),
+            Err(ShmInfoError::Exchange(ExchangeError::TooLarge))
+        );
+    }
+
+    #[test]
+    fn content_eq() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_slice(b"svcexch content"), Status::Ok));
+        assert!(area.content_eq(b"svcexch content", 15));
+        assert!(area.content_eq(b"svcexch other", 8));
+        assert!(!area.content_eq(b"svcexch other", 9));
+        assert!(!area.content_eq(b"svc", 4));
+        assert!(!area.content_eq(&[0u8; EXCHANGE_AREA_LEN + 1], EXCHANGE_AREA_LEN + 1));
+    }
+
+    #[test]
+    fn exchange_section() {
+        if cfg!(feature = "alt-section") {
+            assert_eq!(EXCHANGE_SECTION, ".svcexchange.alt");
+        } else {
+            assert_eq!(EXCHANGE_SECTION, ".svcexchange");
+        }
+    }
+
+    #[test]
+    fn write_bytes_at() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let data = [0x5au8; 32];
+        assert!(matches!(area.write_bytes_at(16, &data), Ok(32)));
+        let mut res = [0u8; 32];
+        assert!(matches!(area.copy_vec_from_at(16, res.as_mut_ptr(), res.len()), Status::Ok));
+        assert_eq!(res, data);
+
+        assert!(matches!(area.write_bytes_at(EXCHANGE_AREA_LEN - 8, &[0xa5u8; 32]), Ok(8)));
+        let mut tail = [0u8; 8];
+        assert!(matches!(
+            area.copy_vec_from_at(EXCHANGE_AREA_LEN - 8, tail.as_mut_ptr(), tail.len()),
+            Status::Ok
+        ));
+        assert_eq!(tail, [0xa5u8; 8]);
+        assert!(matches!(area.write_bytes_at(EXCHANGE_AREA_LEN, &data), Ok(0)));
+
+        assert_fails!(
+            area.write_bytes_at(EXCHANGE_AREA_LEN + 1, &data),
+            Err(Status::TooLarge)
+        );
+    }
+
+    #[test]
+    fn copy_modes() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let fit = [0x11u8; EXCHANGE_AREA_LEN];
+        let over = [0x22u8; EXCHANGE_AREA_LEN + 1];
+        for mode in [CopyMode::Strict, CopyMode::Saturating] {
+            assert!(matches!(area.write_slice_with(&fit, mode), Ok(EXCHANGE_AREA_LEN)));
+        }
+        assert_fails!(area.write_slice_with(&over, CopyMode::Strict), Err(Status::TooLarge));
+        assert!(area.matches(&fit));
+        assert!(matches!(
+            area.write_slice_with(&over, CopyMode::Saturating),
+            Ok(EXCHANGE_AREA_LEN)
+        ));
+        assert!(area.matches(&over[..EXCHANGE_AREA_LEN]));
+
+        let mut res = [0u8; EXCHANGE_AREA_LEN + 1];
+        assert_fails!(area.read_slice_with(&mut res, CopyMode::Strict), Err(Status::TooLarge));
+        assert_eq!(res, [0u8; EXCHANGE_AREA_LEN + 1]);
+        assert!(matches!(
+            area.read_slice_with(&mut res, CopyMode::Saturating),
+            Ok(EXCHANGE_AREA_LEN)
+        ));
+        assert_eq!(res[..EXCHANGE_AREA_LEN], over[..EXCHANGE_AREA_LEN]);
+        assert_eq!(res[EXCHANGE_AREA_LEN], 0);
+        assert!(matches!(
+            area.read_slice_with(&mut res[..EXCHANGE_AREA_LEN], CopyMode::Strict),
+            Ok(EXCHANGE_AREA_LEN)
+        ));
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn trace_hook() {
+        use std::sync::atomic::AtomicUsize;
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+        static READS: AtomicUsize = AtomicUsize::new(0);
+        fn hook(op: ExchangeOp, len: usize) {
+            match op {
+                ExchangeOp::Write => WRITES.store(len, Ordering::Relaxed),
+                ExchangeOp::Read => READS.store(len, Ordering::Relaxed),
+            }
+        }
+
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        Area::set_trace_hook(Some(hook));
+        assert!(matches!(area.write_slice(&[0x42u8; 12]), Status::Ok));
+        assert_eq!(WRITES.load(Ordering::Relaxed), 12);
+        let mut res = [0u8; 5];
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(READS.load(Ordering::Relaxed), 5);
+
+        Area::set_trace_hook(None);
+        assert!(matches!(area.write_slice(&[0x42u8; 3]), Status::Ok));
+        assert_eq!(WRITES.load(Ordering::Relaxed), 12);
+    }
+
+    #[test]
+    fn move_within() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let header = [0xa0u8, 0xa1, 0xa2, 0xa3];
+        let body = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        assert!(matches!(area.write_slice(&header), Status::Ok));
+        assert!(matches!(area.append(&body), Status::Ok));
+        assert!(matches!(area.move_within(4, 8, body.len()), Status::Ok));
+        assert_eq!(area.written_len(), 18);
+        let mut res = [0u8; 10];
+        assert!(matches!(area.copy_vec_from_at(8, res.as_mut_ptr(), res.len()), Status::Ok));
+        assert_eq!(res, body);
+
+        assert_fails!(area.move_within(0, EXCHANGE_AREA_LEN - 3, 4), Status::TooLarge);
+        assert_fails!(area.move_within(EXCHANGE_AREA_LEN - 3, 0, 4), Status::TooLarge);
+        assert_fails!(area.move_within(usize::MAX, 0, 4), Status::Overflow);
+        if cfg!(feature = "strict-read") {
+            assert_fails!(area.move_within(16, 0, 4), Status::ShortRead);
+        }
+    }
+
+    #[test]
+    fn exchange_guard() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let syncs = SYNCS.load(Ordering::Relaxed);
+        {
+            let exchange = area.begin_exchange();
+            assert!(matches!(exchange.write_str("exchange"), Status::Ok));
+            assert_eq!(SYNCS.load(Ordering::Relaxed), syncs);
+        }
+        assert_eq!(SYNCS.load(Ordering::Relaxed), syncs + 1);
+        assert!(area.matches(b"exchange"));
+    }
+
+    #[test]
+    fn shm_info_opt() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let shminfo = ShmInfo::new(3, 0x42, 0x2000_0000, 256, 0x3);
+        assert!(matches!(area.write_shm_info_opt(Some(&shminfo)), Status::Ok));
+        assert_eq!(area.written_len(), 1 + core::mem::size_of::<ShmInfo>());
+        assert!(matches!(area.read_shm_info_opt(), Ok(Some(info)) if info == shminfo));
+
+        assert!(matches!(area.write_shm_info_opt(None), Status::Ok));
+        assert_eq!(area.written_len(), 1);
+        assert!(matches!(area.read_shm_info_opt(), Ok(None)));
+
+        assert!(matches!(area.write_slice(&[2]), Status::Ok));
+        assert_fails!(area.read_shm_info_opt(), Err(Status::Invalid));
+
+        let small = unsafe { Area::<16>::new_sized() };
+        assert_fails!(small.write_shm_info_opt(Some(&shminfo)), Status::TooLarge);
+        assert!(matches!(small.write_slice(&[1]), Status::Ok));
+        assert_fails!(small.read_shm_info_opt(), Err(Status::TooLarge));
+    }
+
+    #[test]
+    fn generic_vec() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let words = [0x0102_0304u32, 0x0506_0708, 0x090a_0b0c];
+        assert!(matches!(area.write_vec(&words), Status::Ok));
+        assert_eq!(area.written_len(), 12);
+        let mut res = [0u32; 3];
+        assert!(matches!(area.read_vec(&mut res), Status::Ok));
+        assert_eq!(res, words);
+
+        assert!(area.matches(&[0x04, 0x03, 0x02, 0x01, 0x08, 0x07, 0x06, 0x05]));
+
+        let pairs = [[0x1122u16, 0x3344], [0x5566, 0x7788]];
+        assert!(matches!(area.write_vec(&pairs), Status::Ok));
+        assert_eq!(area.written_len(), 8);
+        assert!(area.matches(&[0x22, 0x11, 0x44, 0x33, 0x66, 0x55, 0x88, 0x77]));
+        let mut res = [[0u16; 2]; 2];
+        assert!(matches!(area.read_vec(&mut res), Status::Ok));
+        assert_eq!(res, pairs);
+
+        // downstream repr(C) structure
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C)]
+        struct Region {
+            base: u32,
+            len: u16,
+            perms: u16,
+        }
+
+        unsafe impl Pod for Region {
+            fn to_le(self) -> Self {
+                Region { base: self.base.to_le(), len: self.len.to_le(), perms: self.perms.to_le() }
+            }
+
+            fn from_le(value: Self) -> Self {
+                Region {
+                    base: u32::from_le(value.base),
+                    len: u16::from_le(value.len),
+                    perms: u16::from_le(value.perms),
+                }
+            }
+        }
+
+        let regions = [
+            Region { base: 0x2000_0000, len: 0x100, perms: 0x1 },
+            Region { base: 0x2000_1000, len: 0x200, perms: 0x3 },
+        ];
+        assert!(matches!(area.write_vec(&regions), Status::Ok));
+        assert!(area.matches(&[0x00, 0x00, 0x00, 0x20, 0x00, 0x01, 0x01, 0x00]));
+        let mut res = [Region { base: 0, len: 0, perms: 0 }; 2];
+        assert!(matches!(area.read_vec(&mut res), Status::Ok));
+        assert_eq!(res, regions);
+
+        let big = [0u32; EXCHANGE_AREA_LEN / 4 + 1];
+        assert_fails!(area.write_vec(&big), Status::TooLarge);
+        let mut big = [0u32; EXCHANGE_AREA_LEN / 4 + 1];
+        assert_fails!(area.read_vec(&mut big), Status::TooLarge);
+        assert!(matches!(area.write_vec(&[(); usize::MAX]), Status::Ok));
+    }
+
+    #[test]
+    fn peek() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut payload = [0u8; EXCHANGE_AREA_LEN];
+        payload[5] = 0x5a;
+        payload[9..13].copy_from_slice(&0xdead_beefu32.to_le_bytes());
+        payload[EXCHANGE_AREA_LEN - 4..].copy_from_slice(&0x0102_0304u32.to_le_bytes());
+        assert!(matches!(area.write_slice(&payload), Status::Ok));
+        assert!(matches!(area.peek_u8(5), Ok(0x5a)));
+        assert!(matches!(area.peek_u32_le(9), Ok(0xdead_beef)));
+        assert!(matches!(area.peek_u32_le(EXCHANGE_AREA_LEN - 4), Ok(0x0102_0304)));
+        assert_fails!(area.peek_u8(EXCHANGE_AREA_LEN), Err(Status::TooLarge));
+        assert_fails!(area.peek_u32_le(EXCHANGE_AREA_LEN - 3), Err(Status::TooLarge));
+        assert_fails!(area.peek_u32_le(usize::MAX - 1), Err(Status::Overflow));
+
+        let odd = unsafe { Area::<100>::new_sized() };
+        assert!(matches!(odd.write_slice(&payload[..100]), Status::Ok));
+        assert!(odd.peek_u32_le(96).is_ok());
+        assert_fails!(odd.peek_u32_le(97), Err(Status::TooLarge));
+        assert_eq!(odd.get(99), Some(payload[99]));
+        assert_eq!(odd.get(100), None);
+    }
+
+    #[test]
+    fn fill() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_slice(b"payload"), Status::Ok));
+        area.fill(0xcc);
+        assert_eq!(area.written_len(), 0);
+        assert!(area.matches(&[0xccu8; EXCHANGE_AREA_LEN]));
+    }
+
+    #[test]
+    fn get_set() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        area.reset();
+        assert!(area.set(3, 0x33));
+        assert!(area.set(EXCHANGE_AREA_LEN - 1, 0x7f));
+        assert_eq!(area.written_len(), EXCHANGE_AREA_LEN);
+        assert_eq!(area.get(3), Some(0x33));
+        assert_eq!(area.get(EXCHANGE_AREA_LEN - 1), Some(0x7f));
+        assert!(!area.set(EXCHANGE_AREA_LEN, 0x80));
+        assert_eq!(area.get(EXCHANGE_AREA_LEN), None);
+        assert_eq!(area.get(usize::MAX), None);
+    }
+
+    #[test]
+    fn max_elems() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        const MAX_WORDS: usize = <Area>::max_elems::<u32>();
+        assert_eq!(MAX_WORDS, EXCHANGE_AREA_LEN / 4);
+        assert_eq!(MAX_WORDS, <Area as ExchangeArea<u32>>::area_capacity(&area));
+        assert!(matches!(area.write_vec(&[0x5a5a_5a5au32; MAX_WORDS]), Status::Ok));
+        assert_fails!(area.write_vec(&[0u32; MAX_WORDS + 1]), Status::TooLarge);
+        assert_eq!(Area::<16>::max_elems::<u64>(), 2);
+        assert_eq!(<Area>::max_elems::<()>(), usize::MAX);
+    }
+
+    #[test]
+    fn as_bytes_mut() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        {
+            let Ok(bytes) = (unsafe { area.as_bytes_mut(8) }) else {
+                panic!("area view failed");
+            };
+            bytes[..4].copy_from_slice(&0x1234_5678u32.to_le_bytes());
+            bytes[4..].copy_from_slice(b"svc!");
+        }
+        area.sync();
+        assert_eq!(area.written_len(), 8);
+        let mut res = [0u8; 8];
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res[..4], 0x1234_5678u32.to_le_bytes());
+        assert_eq!(&res[4..], b"svc!");
+        assert!(matches!(
+            unsafe { area.as_bytes_mut(EXCHANGE_AREA_LEN + 1) },
+            Err(Status::TooLarge)
+        ));
+    }
+
+    #[test]
+    fn status_to_uapi() {
+        assert_eq!(Status::Ok.to_uapi(), 0);
+        for status in [
+            Status::Invalid,
+            Status::TooLarge,
+            Status::Overlap,
+            Status::NullPointer,
+            Status::Misaligned,
+            Status::Overflow,
+            Status::InvalidUtf8,
+            Status::ShortRead,
+        ] {
+            assert_eq!(status.to_uapi(), 1);
+        }
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn mock_area() {
+        /// println-style component: print msg in area length sized chunks
+        fn print<A: ExchangeArea<u8>>(area: &A, msg: &str, mut syscall: impl FnMut(&A, usize)) {
+            for chunk in msg.as_bytes().chunks(area.area_length()) {
+                assert!(matches!(area.copy_vec_to(chunk.as_ptr(), chunk.len()), Status::Ok));
+                syscall(area, chunk.len());
+            }
+        }
+
+        let area = MockArea::<8>::new();
+        let mut printed = std::vec::Vec::new();
+        print(&area, "hello, svcexch", |area, len| {
+            let mut chunk = [0u8; 8];
+            assert!(matches!(area.copy_vec_from(chunk.as_mut_ptr(), len), Status::Ok));
+            printed.extend_from_slice(&chunk[..len]);
+        });
+        assert_eq!(printed, b"hello, svcexch");
+        assert_eq!(area.write_count(), 2);
+        assert_eq!(&area.contents()[..6], b"vcexch");
+
+        area.load(b"response");
+        let mut res = [0u8; 8];
+        assert!(matches!(area.copy_vec_from(res.as_mut_ptr(), res.len()), Status::Ok));
+        assert_eq!(&res, b"response");
+        assert_fails!(area.copy_vec_to(res.as_ptr(), 9), Status::TooLarge);
+        assert_eq!(area.write_count(), 2);
+    }
+
+    #[test]
+    fn read_slice_counted() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_slice(b"valid data"), Status::Ok));
+        let mut small = [0u8; 5];
+        assert!(matches!(area.read_slice_counted(&mut small), (Status::Ok, 5)));
+        assert_eq!(&small, b"valid");
+        let mut equal = [0u8; 10];
+        assert!(matches!(area.read_slice_counted(&mut equal), (Status::Ok, 10)));
+        assert_eq!(&equal, b"valid data");
+        let mut large = [0xffu8; EXCHANGE_AREA_LEN + 4];
+        assert!(matches!(area.read_slice_counted(&mut large), (Status::Ok, 10)));
+        assert_eq!(&large[..10], b"valid data");
+        assert!(large[10..].iter().all(|&byte| byte == 0xff));
+    }
+
+    #[test]
+    fn pow2_bounds() {
+        const { assert!(Area::<128>::LEN_IS_POW2) };
+        const { assert!(!Area::<100>::LEN_IS_POW2) };
+        const { assert!(!Area::<0>::LEN_IS_POW2) };
+        for idx in [0, 1, 99, 100, 127, 128, 129, 256, usize::MAX] {
+            assert_eq!(Area::<128>::in_area(idx), idx < 128);
+            assert_eq!(Area::<100>::in_area(idx), idx < 100);
+            assert!(!Area::<0>::in_area(idx));
+        }
+    }
+
+    #[test]
+    fn read_length_prefixed() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut dst = [0u8; 16];
+        assert!(matches!(area.write_slice(&[7, 0]), Status::Ok));
+        assert!(matches!(area.append(b"payload"), Status::Ok));
+        assert!(matches!(area.read_length_prefixed(&mut dst), Ok(7)));
+        assert_eq!(&dst[..7], b"payload");
+
+        let too_long = (EXCHANGE_AREA_LEN as u16 - 1).to_le_bytes();
+        assert!(matches!(area.write_slice(&too_long), Status::Ok));
+        let mut big = [0u8; EXCHANGE_AREA_LEN];
+        assert_fails!(area.read_length_prefixed(&mut big), Err(Status::TooLarge));
+
+        assert!(matches!(area.write_slice(&[17, 0]), Status::Ok));
+        assert_fails!(area.read_length_prefixed(&mut dst), Err(Status::TooLarge));
+        assert_fails!(
+            unsafe { Area::<1>::new_sized() }.read_length_prefixed(&mut dst),
+            Err(Status::TooLarge)
+        );
+    }
+
+    #[test]
+    fn write_length_prefixed() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_length_prefixed(b"round trip"), Status::Ok));
+        assert_eq!(area.written_len(), 12);
+        assert!(matches!(area.peek_u8(0), Ok(10)));
+        let mut dst = [0u8; 16];
+        assert!(matches!(area.read_length_prefixed(&mut dst), Ok(10)));
+        assert_eq!(&dst[..10], b"round trip");
+
+        assert!(matches!(area.write_length_prefixed(&[0u8; EXCHANGE_AREA_LEN - 2]), Status::Ok));
+        assert_fails!(
+            area.write_length_prefixed(&[0u8; EXCHANGE_AREA_LEN - 1]),
+            Status::TooLarge
+        );
+        let huge = std::vec![0u8; u16::MAX as usize + 1];
+        assert_fails!(area.write_length_prefixed(&huge), Status::TooLarge);
+    }
+
+    #[test]
+    fn scatter_write() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let header = [0x01u8, 0x02, 0x03, 0x04];
+        let body = [0x55u8; 32];
+        let trailer = *b"end";
+        assert!(matches!(area.scatter_write(&[&header, &body, &trailer]), Ok(39)));
+        assert_eq!(area.written_len(), 39);
+        let mut expected = [0u8; 39];
+        expected[..4].copy_from_slice(&header);
+        expected[4..36].copy_from_slice(&body);
+        expected[36..].copy_from_slice(&trailer);
+        assert!(area.matches(&expected));
+
+        let big = [0xaau8; EXCHANGE_AREA_LEN - 35];
+        assert_fails!(area.scatter_write(&[&header, &body, &big]), Err(Status::TooLarge));
+        assert!(area.matches(&expected));
+        assert_eq!(area.written_len(), 39);
+    }
+
+    #[test]
+    fn scatter_read() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let mut payload = [0u8; 64];
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        assert!(matches!(area.write_slice(&payload), Status::Ok));
+        let mut head = [0u8; 16];
+        let mut tail = [0u8; 48];
+        assert!(matches!(area.scatter_read(&mut [&mut head, &mut tail]), Ok(64)));
+        assert_eq!(head, payload[..16]);
+        assert_eq!(tail, payload[16..]);
+
+        let mut big = [0u8; EXCHANGE_AREA_LEN - 15];
+        assert_fails!(area.scatter_read(&mut [&mut head, &mut big]), Err(Status::TooLarge));
+        if cfg!(feature = "strict-read") {
+            let mut more = [0u8; 49];
+            assert_fails!(
+                area.scatter_read(&mut [&mut head, &mut more]),
+                Err(Status::ShortRead)
+            );
+        }
+    }
+
+    #[test]
+    fn exchange_layout() {
+        exchange_layout! {
+            struct Req {
+                header: u32,
+                body: [u8; 32],
+                flags: u16,
+            }
+        }
+
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert_eq!(Req::header.offset(), 0);
+        assert_eq!(Req::body.offset(), 4);
+        assert_eq!(Req::flags.offset(), 36);
+        assert_eq!(Req::SIZE, 38);
+        area.reset();
+        assert!(matches!(Req::body.write(&area, &[0x5a; 32]), Status::Ok));
+        assert!(matches!(Req::header.write(&area, &0xdead_beef), Status::Ok));
+        assert!(matches!(Req::flags.write(&area, &0x0102), Status::Ok));
+        assert_eq!(area.written_len(), Req::SIZE);
+        // the fields are stored in little-endian byte order
+        assert!(area.matches(&[0xef, 0xbe, 0xad, 0xde, 0x5a]));
+        assert!(matches!(area.peek::<2>(Req::flags.offset()), Ok([0x02, 0x01])));
+        assert!(matches!(Req::header.read(&area), Ok(0xdead_beef)));
+        assert!(matches!(Req::body.read(&area), Ok(body) if body == [0x5a; 32]));
+        assert!(matches!(Req::flags.read(&area), Ok(0x0102)));
+
+        let small = unsafe { Area::<32>::new_sized() };
+        assert_fails!(Req::body.write(&small, &[0; 32]), Status::TooLarge);
+        assert_fails!(Req::body.read(&small), Err(Status::TooLarge));
+    }
+
+    #[test]
+    fn base_addr() {
+        let area = unsafe { Area::new() };
+        assert_ne!(area.base_addr(), 0);
+        assert_eq!(area.base_addr() % 8, 0);
+    }
+
+    #[test]
+    fn is_disjoint() {
+        let area = unsafe { Area::new() };
+        let base = EXCHANGE_AREA.as_ptr() as usize;
+        let end = base + EXCHANGE_AREA_LEN * AREA_SLOTS;
+        let buffer = [0u8; 16];
+        assert!(area.is_disjoint(buffer.as_ptr(), buffer.len()));
+        assert!(area.is_disjoint((base - 16) as *const u8, 16));
+        assert!(!area.is_disjoint((base - 16) as *const u8, 17));
+        assert!(area.is_disjoint(end as *const u8, 16));
+        assert!(!area.is_disjoint((end - 1) as *const u8, 16));
+        assert!(!area.is_disjoint((base + 8) as *const u8, 8));
+        assert!(!area.is_disjoint((base - 16) as *const u8, EXCHANGE_AREA_LEN * AREA_SLOTS + 32));
+        assert!(!area.is_disjoint(usize::MAX as *const u8, 2));
+    }
+
+    #[test]
+    fn caller_storage() {
+        #[repr(C, align(8))]
+        struct Storage([u8; 32]);
+
+        let _guard = lock_area();
+        let global = unsafe { Area::new() };
+        assert!(matches!(global.write_slice(b"global"), Status::Ok));
+
+        let mut storage = Storage([0u8; 32]);
+        let base = storage.0.as_mut_ptr();
+        let Ok(area) = (unsafe { Area::<32>::from_raw_parts(base, 32) }) else {
+            panic!("caller storage rejected");
         };
-        area.copy_to(&shminfo);
-        area.copy_from(&mut shminfo_copy);
-        assert_eq!(shminfo, shminfo_copy);
+        assert_eq!(area.base_addr(), base as usize);
+        assert!(matches!(area.write_slice(&[0x42u8; 16]), Status::Ok));
+        assert!(matches!(area.copy_vec_to_at(16, b"caller".as_ptr(), 6), Status::Ok));
+        let mut res = [0u8; 22];
+        assert!(matches!(area.read_slice(&mut res), Status::Ok));
+        assert_eq!(res[..16], [0x42u8; 16]);
+        assert_eq!(&res[16..], b"caller");
+        assert!(!area.is_disjoint(base, 1));
+        assert_fails!(area.write_slice(&[0u8; 33]), Status::TooLarge);
+        drop(area);
+        assert_eq!(&storage.0[16..22], b"caller");
+        assert!(global.matches(b"global"));
+
+        assert!(matches!(
+            unsafe { Area::<32>::from_raw_parts(core::ptr::null_mut(), 32) },
+            Err(Status::NullPointer)
+        ));
+        assert!(matches!(
+            unsafe { Area::<16>::from_raw_parts(storage.0.as_mut_ptr().add(1), 16) },
+            Err(Status::Misaligned)
+        ));
+        assert!(matches!(
+            unsafe { Area::<32>::from_raw_parts(storage.0.as_mut_ptr(), 16) },
+            Err(Status::TooLarge)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_vec() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_slice(b"snapshot me"), Status::Ok));
+        assert_eq!(area.to_vec(11), b"snapshot me");
+        assert_eq!(area.to_vec(4), b"snap");
+        assert_eq!(area.to_vec(EXCHANGE_AREA_LEN + 16).len(), EXCHANGE_AREA_LEN);
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_slice(b"exchange A"), Status::Ok));
+        let snap = area.snapshot();
+        assert!(matches!(area.write_slice(b"exchange B, longer"), Status::Ok));
+        assert!(area.matches(b"exchange B, longer"));
+        assert!(matches!(area.restore(&snap), Status::Ok));
+        assert_eq!(area.written_len(), EXCHANGE_AREA_LEN);
+        assert!(area.matches(&snap));
+        assert!(area.matches(b"exchange A"));
+    }
+
+    #[test]
+    fn zero_length_copies() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        assert!(matches!(area.write_slice(b"kept"), Status::Ok));
+        let null = core::ptr::null_mut::<u8>();
+        assert!(matches!(area.copy_vec_to(null, 0), Status::Ok));
+        assert!(matches!(area.copy_vec_from(null, 0), Status::Ok));
+        assert!(matches!(area.copy_vec_to_counted(null, 0), (Status::Ok, 0)));
+        assert!(matches!(area.copy_vec_from_counted(null, 0), (Status::Ok, 0)));
+        assert!(matches!(area.copy_vec_to_at(EXCHANGE_AREA_LEN, null, 0), Status::Ok));
+        assert!(matches!(area.copy_vec_from_at(EXCHANGE_AREA_LEN, null, 0), Status::Ok));
+        let shm = core::ptr::null_mut::<ShmInfo>();
+        assert!(matches!(area.copy_vec_to(shm, 0), Status::Ok));
+        assert!(matches!(area.copy_vec_from(shm, 0), Status::Ok));
+        assert!(matches!(area.write_vec::<u32>(&[]), Status::Ok));
+        assert!(matches!(area.write_vec(&[(); 4]), Status::Ok));
+        assert_eq!(area.written_len(), 4);
+        assert!(area.matches(b"kept"));
+    }
+
+    #[cfg(feature = "panic-on-error")]
+    #[test]
+    #[should_panic(expected = "copy to the area of 16 bytes at offset 120 failed")]
+    fn panic_on_error() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let _ = area.copy_vec_to_at(120, [0u8; 16].as_ptr(), 16);
+    }
+
+    #[cfg(feature = "panic-on-error")]
+    #[test]
+    #[should_panic(expected = "copy to the area of 200 bytes at offset 0 failed")]
+    fn panic_on_error_slice() {
+        let _ = unsafe { Area::<2>::new_sized() }.write_slice(&[0; 200]);
+    }
+
+    #[cfg(feature = "panic-on-error")]
+    #[test]
+    #[should_panic(expected = "copy to the area of 4 bytes at offset 0 failed")]
+    fn panic_on_error_typed() {
+        let _ = unsafe { Area::<2>::new_sized() }.copy_to(&0x0102_0304u32);
+    }
+
+    #[cfg(feature = "panic-on-error")]
+    #[test]
+    #[should_panic(expected = "copy to the area of 2 bytes at offset 1 failed")]
+    fn panic_on_error_append() {
+        let area = unsafe { Area::<2>::new_sized() };
+        assert!(matches!(area.append(&[1]), Status::Ok));
+        let _ = area.append(&[2, 3]);
+    }
+
+    #[test]
+    fn shm_info_max_in_area() {
+        let area = unsafe { Area::new() };
+        assert_eq!(ShmInfo::MAX_IN_AREA, EXCHANGE_AREA_LEN / core::mem::size_of::<ShmInfo>());
+        assert_eq!(ShmInfo::MAX_IN_AREA, <Area as ExchangeArea<ShmInfo>>::area_capacity(&area));
+        let infos = [ShmInfo::default(); ShmInfo::MAX_IN_AREA];
+        assert_eq!(infos.len(), if cfg!(target_pointer_width = "64") { 4 } else { 6 });
+    }
+
+    #[test]
+    fn status_fmt() {
+        assert_eq!(std::format!("{:?}", Status::Ok), "Ok");
+        assert_eq!(std::format!("{:?}", Status::TooLarge), "TooLarge");
+        assert_eq!(std::format!("{}", Status::Ok), "ok");
+        assert_eq!(std::format!("{}", Status::Overlap), "buffer overlaps exchange area");
+        assert_eq!(
+            std::format!("{}", Status::ShortRead),
+            std::format!("{}", ExchangeError::ShortRead)
+        );
+    }
+
+    #[test]
+    fn read_vec_bytes() {
+        let _guard = lock_area();
+        let area = unsafe { Area::new() };
+        let words = [0x1111_1111u32, 0x2222_2222, 0x3333_3333];
+        assert!(matches!(area.write_vec(&words), Status::Ok));
+        let mut res = [0u32; 4];
+        assert!(matches!(area.read_vec_bytes(&mut res, 8), Status::Ok));
+        assert_eq!(res, [0x1111_1111, 0x2222_2222, 0, 0]);
+        assert_fails!(area.read_vec_bytes(&mut res, 10), Status::Invalid);
+        assert_fails!(area.read_vec_bytes(&mut res, 3), Status::Invalid);
+        assert_fails!(area.read_vec_bytes(&mut res, 20), Status::TooLarge);
+        assert_fails!(area.read_vec_bytes(&mut [(); 2], 1), Status::Invalid);
+        assert_eq!(res, [0x1111_1111, 0x2222_2222, 0, 0]);
+
+        let mut triples = [[0u8; 3]; 4];
+        assert!(matches!(area.read_vec_bytes(&mut triples, 6), Status::Ok));
+        assert_eq!(triples[..2], [[0x11; 3], [0x11, 0x22, 0x22]]);
+        assert_fails!(area.read_vec_bytes(&mut triples, 8), Status::Invalid);
     }
 }